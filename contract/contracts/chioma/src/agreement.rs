@@ -1,10 +1,12 @@
 //! Agreement management logic for the Chioma/Rental contract.
-use soroban_sdk::{Address, Env, Map, String};
+use soroban_sdk::{Address, Env, Map, String, Symbol, Val};
 
 use crate::errors::RentalError;
-use crate::events::{AgreementCreatedEvent, AgreementSigned};
-use crate::storage::DataKey;
-use crate::types::{AgreementStatus, PaymentSplit, RentAgreement};
+use crate::events::{AgreementCreatedEvent, AgreementSigned, AgreementStatusChanged};
+use crate::storage::{DataKey, PAYMENT_SPLIT_TTL_EXTEND_TO, PAYMENT_SPLIT_TTL_THRESHOLD};
+use crate::types::{
+    AgreementStatus, BillingCycle, LegacyRentAgreement, PaymentSplit, RentAgreement,
+};
 
 /// Validate agreement parameters
 pub fn validate_agreement_params(
@@ -82,7 +84,7 @@ pub fn create_agreement(
         signed_at: None,
         payment_token,
         next_payment_due: start_date,
-        payment_history: Map::new(env),
+        billing_cycle: BillingCycle::CalendarMonth,
     };
 
     // Store agreement
@@ -107,6 +109,140 @@ pub fn create_agreement(
     Ok(())
 }
 
+/// Check a status change against the agreement lifecycle transition table.
+///
+/// Draft -> Pending | Cancelled
+/// Pending -> Active | Cancelled
+/// Active -> Completed | Terminated | Disputed
+/// Disputed -> Active | Terminated
+pub fn validate_transition(
+    from: &AgreementStatus,
+    to: &AgreementStatus,
+) -> Result<(), RentalError> {
+    use AgreementStatus::*;
+
+    let allowed = matches!(
+        (from, to),
+        (Draft, Pending)
+            | (Draft, Cancelled)
+            | (Pending, Active)
+            | (Pending, Cancelled)
+            | (Active, Completed)
+            | (Active, Terminated)
+            | (Active, Disputed)
+            | (Disputed, Active)
+            | (Disputed, Terminated)
+    );
+
+    if allowed {
+        Ok(())
+    } else {
+        Err(RentalError::InvalidState)
+    }
+}
+
+/// Move an agreement to a new status, persist it and emit a status change event
+fn transition(
+    env: &Env,
+    agreement: &mut RentAgreement,
+    to: AgreementStatus,
+    actor: &Address,
+) -> Result<(), RentalError> {
+    validate_transition(&agreement.status, &to)?;
+
+    let from = agreement.status.clone();
+    agreement.status = to.clone();
+
+    env.storage().persistent().set(
+        &DataKey::Agreement(agreement.agreement_id.clone()),
+        agreement,
+    );
+
+    AgreementStatusChanged {
+        agreement_id: agreement.agreement_id.clone(),
+        from_status: from,
+        to_status: to,
+        actor: actor.clone(),
+        changed_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Load an agreement and verify the caller is its landlord
+fn load_as_landlord(
+    env: &Env,
+    landlord: &Address,
+    agreement_id: &String,
+) -> Result<RentAgreement, RentalError> {
+    // Landlord MUST authorize lifecycle changes
+    landlord.require_auth();
+
+    let agreement: RentAgreement = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Agreement(agreement_id.clone()))
+        .ok_or(RentalError::AgreementNotFound)?;
+
+    if agreement.landlord != *landlord {
+        return Err(RentalError::NotLandlord);
+    }
+
+    Ok(agreement)
+}
+
+/// Change how a Draft agreement's due dates are spaced. Agreements bill by
+/// calendar month unless the landlord sets a fixed period before submitting.
+pub fn set_billing_cycle(
+    env: &Env,
+    landlord: Address,
+    agreement_id: String,
+    billing_cycle: BillingCycle,
+) -> Result<(), RentalError> {
+    let mut agreement = load_as_landlord(env, &landlord, &agreement_id)?;
+
+    if agreement.status != AgreementStatus::Draft {
+        return Err(RentalError::InvalidState);
+    }
+    if billing_cycle == BillingCycle::FixedPeriod(0) {
+        return Err(RentalError::InvalidBillingCycle);
+    }
+
+    agreement.billing_cycle = billing_cycle;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Agreement(agreement_id), &agreement);
+
+    Ok(())
+}
+
+/// Submit a Draft agreement to the tenant for signature
+pub fn submit_agreement(
+    env: &Env,
+    landlord: Address,
+    agreement_id: String,
+) -> Result<(), RentalError> {
+    let mut agreement = load_as_landlord(env, &landlord, &agreement_id)?;
+
+    // An offer that can no longer be signed must not be submitted
+    if env.ledger().timestamp() > agreement.end_date {
+        return Err(RentalError::Expired);
+    }
+
+    transition(env, &mut agreement, AgreementStatus::Pending, &landlord)
+}
+
+/// Cancel a Draft or Pending agreement before the tenant signs it
+pub fn cancel_agreement(
+    env: &Env,
+    landlord: Address,
+    agreement_id: String,
+) -> Result<(), RentalError> {
+    let mut agreement = load_as_landlord(env, &landlord, &agreement_id)?;
+    transition(env, &mut agreement, AgreementStatus::Cancelled, &landlord)
+}
+
 /// Sign an agreement as the tenant
 pub fn sign_agreement(env: &Env, tenant: Address, agreement_id: String) -> Result<(), RentalError> {
     // Tenant MUST authorize signing
@@ -124,10 +260,8 @@ pub fn sign_agreement(env: &Env, tenant: Address, agreement_id: String) -> Resul
         return Err(RentalError::NotTenant);
     }
 
-    // Validate agreement is in Pending status
-    if agreement.status != AgreementStatus::Pending {
-        return Err(RentalError::InvalidState);
-    }
+    // Validate agreement can move to Active (only from Pending)
+    validate_transition(&agreement.status, &AgreementStatus::Active)?;
 
     // Validate agreement has not expired
     let current_time = env.ledger().timestamp();
@@ -135,14 +269,9 @@ pub fn sign_agreement(env: &Env, tenant: Address, agreement_id: String) -> Resul
         return Err(RentalError::Expired);
     }
 
-    // Update agreement status and record signing time
-    agreement.status = AgreementStatus::Active;
+    // Record signing time, then activate and persist
     agreement.signed_at = Some(current_time);
-
-    // Save updated agreement
-    env.storage()
-        .persistent()
-        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    transition(env, &mut agreement, AgreementStatus::Active, &tenant)?;
 
     // Emit AgreementSigned event
     AgreementSigned {
@@ -156,6 +285,31 @@ pub fn sign_agreement(env: &Env, tenant: Address, agreement_id: String) -> Resul
     Ok(())
 }
 
+/// Complete an Active agreement once its end date has passed
+pub fn complete_agreement(
+    env: &Env,
+    landlord: Address,
+    agreement_id: String,
+) -> Result<(), RentalError> {
+    let mut agreement = load_as_landlord(env, &landlord, &agreement_id)?;
+
+    if env.ledger().timestamp() < agreement.end_date {
+        return Err(RentalError::AgreementNotEnded);
+    }
+
+    transition(env, &mut agreement, AgreementStatus::Completed, &landlord)
+}
+
+/// Terminate an Active agreement before its end date
+pub fn terminate_agreement(
+    env: &Env,
+    landlord: Address,
+    agreement_id: String,
+) -> Result<(), RentalError> {
+    let mut agreement = load_as_landlord(env, &landlord, &agreement_id)?;
+    transition(env, &mut agreement, AgreementStatus::Terminated, &landlord)
+}
+
 /// Retrieve a rent agreement by its unique identifier
 pub fn get_agreement(env: &Env, agreement_id: String) -> Option<RentAgreement> {
     env.storage()
@@ -178,20 +332,74 @@ pub fn get_agreement_count(env: &Env) -> u32 {
         .unwrap_or(0)
 }
 
-/// Get payment split for a specific month in an agreement
+/// Store the split of an agreement's payment for `month` in its own entry
+pub fn save_payment_split(env: &Env, agreement_id: &String, month: u32, split: &PaymentSplit) {
+    let key = DataKey::PaymentSplit(agreement_id.clone(), month);
+    let storage = env.storage().persistent();
+    storage.set(&key, split);
+    storage.extend_ttl(
+        &key,
+        PAYMENT_SPLIT_TTL_THRESHOLD,
+        PAYMENT_SPLIT_TTL_EXTEND_TO,
+    );
+}
+
+/// Get payment split for a specific month in an agreement.
+/// Each month is stored in its own entry, so only that entry is read.
 pub fn get_payment_split(
     env: &Env,
     agreement_id: String,
     month: u32,
 ) -> Result<PaymentSplit, RentalError> {
-    let agreement: RentAgreement = env
+    env.storage()
+        .persistent()
+        .get(&DataKey::PaymentSplit(agreement_id, month))
+        .ok_or(RentalError::PaymentNotFound)
+}
+
+/// Rewrite an agreement stored in the legacy layout, which embedded its payment
+/// history, into the current one. Each month's split moves to its own entry and the
+/// agreement is billed by calendar month from its start date. Returns whether the
+/// agreement was rewritten; agreements already in the current layout, or missing,
+/// are left alone.
+pub fn migrate_agreement(env: &Env, agreement_id: &String) -> bool {
+    let key = DataKey::Agreement(agreement_id.clone());
+    let Some(fields) = env.storage().persistent().get::<_, Map<Symbol, Val>>(&key) else {
+        return false;
+    };
+    if !fields.contains_key(Symbol::new(env, "payment_history")) {
+        return false;
+    }
+    let Some(legacy) = env
         .storage()
         .persistent()
-        .get(&DataKey::Agreement(agreement_id))
-        .ok_or(RentalError::AgreementNotFound)?;
+        .get::<_, LegacyRentAgreement>(&key)
+    else {
+        return false;
+    };
+
+    for (month, split) in legacy.payment_history.iter() {
+        save_payment_split(env, agreement_id, month, &split);
+    }
 
-    agreement
-        .payment_history
-        .get(month)
-        .ok_or(RentalError::AgreementNotFound)
+    let agreement = RentAgreement {
+        agreement_id: legacy.agreement_id,
+        landlord: legacy.landlord,
+        tenant: legacy.tenant,
+        agent: legacy.agent,
+        monthly_rent: legacy.monthly_rent,
+        security_deposit: legacy.security_deposit,
+        start_date: legacy.start_date,
+        end_date: legacy.end_date,
+        agent_commission_rate: legacy.agent_commission_rate,
+        status: legacy.status,
+        total_rent_paid: legacy.total_rent_paid,
+        payment_count: legacy.payment_count,
+        signed_at: legacy.signed_at,
+        payment_token: legacy.payment_token,
+        next_payment_due: legacy.next_payment_due,
+        billing_cycle: BillingCycle::CalendarMonth,
+    };
+    env.storage().persistent().set(&key, &agreement);
+    true
 }
@@ -16,4 +16,8 @@ pub enum RentalError {
     NotTenant = 14,
     InvalidState = 15,
     Expired = 16,
+    NotLandlord = 17,
+    AgreementNotEnded = 18,
+    InvalidBillingCycle = 19,
+    PaymentNotFound = 20,
 }
@@ -1,4 +1,4 @@
-use crate::Config;
+use crate::{AgreementStatus, Config};
 use soroban_sdk::{contractevent, Address, Env, String};
 
 #[contractevent]
@@ -16,6 +16,16 @@ pub struct AgreementSigned {
     pub signed_at: u64,
 }
 
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AgreementStatusChanged {
+    pub agreement_id: String,
+    pub from_status: AgreementStatus,
+    pub to_status: AgreementStatus,
+    pub actor: Address,
+    pub changed_at: u64,
+}
+
 #[contractevent]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ContractInitialized {
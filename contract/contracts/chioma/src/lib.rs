@@ -1,7 +1,7 @@
 #![no_std]
 #![allow(clippy::too_many_arguments)]
 
-use soroban_sdk::{contract, contractimpl, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
 
 mod agreement;
 mod errors;
@@ -13,13 +13,17 @@ mod types;
 mod tests;
 
 pub use agreement::{
-    create_agreement, get_agreement, get_agreement_count, get_payment_split, has_agreement,
-    sign_agreement, validate_agreement_params,
+    cancel_agreement, complete_agreement, create_agreement, get_agreement, get_agreement_count,
+    get_payment_split, has_agreement, migrate_agreement, set_billing_cycle, sign_agreement,
+    submit_agreement, terminate_agreement, validate_agreement_params, validate_transition,
 };
 pub use errors::RentalError;
-pub use events::{AgreementCreatedEvent, AgreementSigned, ConfigUpdated};
+pub use events::{AgreementCreatedEvent, AgreementSigned, AgreementStatusChanged, ConfigUpdated};
 pub use storage::DataKey;
-pub use types::{AgreementStatus, Config, ContractState, PaymentSplit, RentAgreement};
+pub use types::{
+    AgreementStatus, BillingCycle, Config, ContractState, LegacyRentAgreement, PaymentSplit,
+    RentAgreement,
+};
 
 #[contract]
 pub struct Contract;
@@ -119,6 +123,52 @@ impl Contract {
         )
     }
 
+    /// Set how a Draft agreement's due dates are spaced from its start date.
+    /// New agreements bill by calendar month.
+    ///
+    /// # Errors
+    /// * `AgreementNotFound` - If no agreement exists for the identifier
+    /// * `NotLandlord` - If the caller is not the agreement's landlord
+    /// * `InvalidState` - If the agreement is not in Draft
+    /// * `InvalidBillingCycle` - If a fixed period has zero length
+    pub fn set_billing_cycle(
+        env: Env,
+        landlord: Address,
+        agreement_id: String,
+        billing_cycle: BillingCycle,
+    ) -> Result<(), RentalError> {
+        agreement::set_billing_cycle(&env, landlord, agreement_id, billing_cycle)
+    }
+
+    /// Submit a Draft agreement to the tenant for signature (Draft -> Pending).
+    ///
+    /// # Errors
+    /// * `AgreementNotFound` - If no agreement exists for the identifier
+    /// * `NotLandlord` - If the caller is not the agreement's landlord
+    /// * `Expired` - If the agreement end date has already passed
+    /// * `InvalidState` - If the agreement is not in Draft
+    pub fn submit_agreement(
+        env: Env,
+        landlord: Address,
+        agreement_id: String,
+    ) -> Result<(), RentalError> {
+        agreement::submit_agreement(&env, landlord, agreement_id)
+    }
+
+    /// Withdraw an unsigned offer (Draft or Pending -> Cancelled).
+    ///
+    /// # Errors
+    /// * `AgreementNotFound` - If no agreement exists for the identifier
+    /// * `NotLandlord` - If the caller is not the agreement's landlord
+    /// * `InvalidState` - If the agreement is not in Draft or Pending
+    pub fn cancel_agreement(
+        env: Env,
+        landlord: Address,
+        agreement_id: String,
+    ) -> Result<(), RentalError> {
+        agreement::cancel_agreement(&env, landlord, agreement_id)
+    }
+
     pub fn sign_agreement(
         env: Env,
         tenant: Address,
@@ -127,6 +177,35 @@ impl Contract {
         agreement::sign_agreement(&env, tenant, agreement_id)
     }
 
+    /// Close out an agreement that has run its course (Active -> Completed).
+    ///
+    /// # Errors
+    /// * `AgreementNotFound` - If no agreement exists for the identifier
+    /// * `NotLandlord` - If the caller is not the agreement's landlord
+    /// * `AgreementNotEnded` - If the end date has not been reached yet
+    /// * `InvalidState` - If the agreement is not Active
+    pub fn complete_agreement(
+        env: Env,
+        landlord: Address,
+        agreement_id: String,
+    ) -> Result<(), RentalError> {
+        agreement::complete_agreement(&env, landlord, agreement_id)
+    }
+
+    /// End an agreement early (Active -> Terminated).
+    ///
+    /// # Errors
+    /// * `AgreementNotFound` - If no agreement exists for the identifier
+    /// * `NotLandlord` - If the caller is not the agreement's landlord
+    /// * `InvalidState` - If the agreement is not Active
+    pub fn terminate_agreement(
+        env: Env,
+        landlord: Address,
+        agreement_id: String,
+    ) -> Result<(), RentalError> {
+        agreement::terminate_agreement(&env, landlord, agreement_id)
+    }
+
     pub fn get_agreement(env: Env, agreement_id: String) -> Option<RentAgreement> {
         agreement::get_agreement(&env, agreement_id)
     }
@@ -139,6 +218,11 @@ impl Contract {
         agreement::get_agreement_count(&env)
     }
 
+    /// Get the split of an agreement's payment for `month` (0-based). Only that
+    /// month's entry is read.
+    ///
+    /// # Errors
+    /// * `PaymentNotFound` - If no payment is recorded for the month
     pub fn get_payment_split(
         env: Env,
         agreement_id: String,
@@ -146,4 +230,24 @@ impl Contract {
     ) -> Result<PaymentSplit, RentalError> {
         agreement::get_payment_split(&env, agreement_id, month)
     }
+
+    /// Rewrite agreements stored in the legacy layout, which embedded the payment
+    /// history, into the current one (admin only). Each month's split moves to its
+    /// own `get_payment_split` entry. Agreements already migrated are skipped, so the
+    /// call can be repeated safely. Returns the number of agreements rewritten.
+    ///
+    /// # Errors
+    /// * `InvalidState` - If contract state is missing
+    pub fn migrate_agreements(env: Env, agreement_ids: Vec<String>) -> Result<u32, RentalError> {
+        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
+        state.admin.require_auth();
+
+        let mut migrated = 0;
+        for agreement_id in agreement_ids.iter() {
+            if agreement::migrate_agreement(&env, &agreement_id) {
+                migrated += 1;
+            }
+        }
+        Ok(migrated)
+    }
 }
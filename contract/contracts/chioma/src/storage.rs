@@ -1,9 +1,19 @@
 use soroban_sdk::{contracttype, String};
 
+/// Ledgers per day at a 5 second close time
+const DAY_IN_LEDGERS: u32 = 17_280;
+/// Lifetime (in ledgers) a payment split entry is extended to, about 30 days.
+/// Expired entries are archived, not deleted, and can be restored.
+pub const PAYMENT_SPLIT_TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+/// Remaining lifetime (in ledgers) below which a payment split entry is extended
+pub const PAYMENT_SPLIT_TTL_THRESHOLD: u32 = PAYMENT_SPLIT_TTL_EXTEND_TO - DAY_IN_LEDGERS;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     Agreement(String),
+    /// Payment split by agreement ID and month (0-based)
+    PaymentSplit(String, u32),
     AgreementCount,
     State,
 }
@@ -1,7 +1,7 @@
 use super::*;
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
-    Address, Env, IntoVal, String,
+    Address, Env, Event, IntoVal, Map, String, Symbol, TryFromVal, Val,
 };
 
 #[test]
@@ -459,17 +459,7 @@ fn create_pending_agreement(
         &Address::generate(env),
     );
 
-    let mut agreement = client
-        .get_agreement(&String::from_str(env, agreement_id))
-        .unwrap();
-    agreement.status = AgreementStatus::Pending;
-
-    env.as_contract(&client.address, || {
-        env.storage().persistent().set(
-            &storage::DataKey::Agreement(String::from_str(env, agreement_id)),
-            &agreement,
-        );
-    });
+    client.submit_agreement(landlord, &String::from_str(env, agreement_id));
 }
 
 #[test]
@@ -631,6 +621,345 @@ fn test_sign_agreement_event_emission() {
     assert!(events_after.len() > events_before);
 }
 
+#[test]
+fn test_transition_table() {
+    use AgreementStatus::*;
+
+    assert!(validate_transition(&Draft, &Pending).is_ok());
+    assert!(validate_transition(&Draft, &Cancelled).is_ok());
+    assert!(validate_transition(&Pending, &Active).is_ok());
+    assert!(validate_transition(&Pending, &Cancelled).is_ok());
+    assert!(validate_transition(&Active, &Completed).is_ok());
+    assert!(validate_transition(&Active, &Terminated).is_ok());
+
+    assert_eq!(
+        validate_transition(&Draft, &Active),
+        Err(RentalError::InvalidState)
+    );
+    assert_eq!(
+        validate_transition(&Active, &Cancelled),
+        Err(RentalError::InvalidState)
+    );
+    assert_eq!(
+        validate_transition(&Completed, &Active),
+        Err(RentalError::InvalidState)
+    );
+    assert_eq!(
+        validate_transition(&Cancelled, &Pending),
+        Err(RentalError::InvalidState)
+    );
+}
+
+#[test]
+fn test_submit_agreement_success() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+
+    let agreement_id = "SUBMIT_001";
+    create_pending_agreement(&env, &client, agreement_id, &tenant, &landlord);
+
+    let agreement = client
+        .get_agreement(&String::from_str(&env, agreement_id))
+        .unwrap();
+    assert_eq!(agreement.status, AgreementStatus::Pending);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_submit_agreement_not_landlord() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let agreement_id = String::from_str(&env, "SUBMIT_002");
+
+    client.create_agreement(
+        &agreement_id,
+        &landlord,
+        &tenant,
+        &None,
+        &1000,
+        &2000,
+        &100,
+        &1000000,
+        &0,
+        &Address::generate(&env),
+    );
+
+    client.submit_agreement(&tenant, &agreement_id);
+}
+
+#[test]
+fn test_set_billing_cycle_on_draft() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let agreement_id = String::from_str(&env, "CYCLE_001");
+
+    client.create_agreement(
+        &agreement_id,
+        &landlord,
+        &tenant,
+        &None,
+        &1000,
+        &2000,
+        &100,
+        &1000000,
+        &0,
+        &Address::generate(&env),
+    );
+    let agreement = client.get_agreement(&agreement_id).unwrap();
+    assert_eq!(agreement.billing_cycle, BillingCycle::CalendarMonth);
+
+    assert_eq!(
+        client.try_set_billing_cycle(&landlord, &agreement_id, &BillingCycle::FixedPeriod(0)),
+        Err(Ok(RentalError::InvalidBillingCycle))
+    );
+    client.set_billing_cycle(
+        &landlord,
+        &agreement_id,
+        &BillingCycle::FixedPeriod(604_800),
+    );
+    let agreement = client.get_agreement(&agreement_id).unwrap();
+    assert_eq!(agreement.billing_cycle, BillingCycle::FixedPeriod(604_800));
+
+    // The cycle is fixed once the offer goes to the tenant
+    client.submit_agreement(&landlord, &agreement_id);
+    assert_eq!(
+        client.try_set_billing_cycle(&landlord, &agreement_id, &BillingCycle::CalendarMonth),
+        Err(Ok(RentalError::InvalidState))
+    );
+}
+
+#[test]
+fn test_transition_emits_status_changed_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let agreement_id = String::from_str(&env, "EVENT_001");
+
+    client.create_agreement(
+        &agreement_id,
+        &landlord,
+        &tenant,
+        &None,
+        &1000,
+        &2000,
+        &100,
+        &1000000,
+        &0,
+        &Address::generate(&env),
+    );
+
+    client.submit_agreement(&landlord, &agreement_id);
+
+    let events = env.events().all();
+    assert_eq!(events.len(), 1);
+
+    let (contract, topics, data) = events.get_unchecked(0);
+    assert_eq!(contract, client.address);
+    let topic = Symbol::try_from_val(&env, &topics.get_unchecked(0)).unwrap();
+    assert_eq!(topic, Symbol::new(&env, "agreement_status_changed"));
+
+    let payload = Map::<Symbol, Val>::try_from_val(&env, &data).unwrap();
+    let field = |name: &str| payload.get(Symbol::new(&env, name)).unwrap();
+    assert_eq!(
+        String::try_from_val(&env, &field("agreement_id")).unwrap(),
+        agreement_id
+    );
+    assert_eq!(
+        AgreementStatus::try_from_val(&env, &field("from_status")).unwrap(),
+        AgreementStatus::Draft
+    );
+    assert_eq!(
+        AgreementStatus::try_from_val(&env, &field("to_status")).unwrap(),
+        AgreementStatus::Pending
+    );
+
+    let expected = AgreementStatusChanged {
+        agreement_id,
+        from_status: AgreementStatus::Draft,
+        to_status: AgreementStatus::Pending,
+        actor: landlord,
+        changed_at: env.ledger().timestamp(),
+    };
+    assert!(events.contains((
+        client.address.clone(),
+        expected.topics(&env),
+        expected.data(&env)
+    )));
+}
+
+#[test]
+fn test_cancel_draft_and_pending_agreements() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+
+    let draft_id = String::from_str(&env, "CANCEL_001");
+    client.create_agreement(
+        &draft_id,
+        &landlord,
+        &tenant,
+        &None,
+        &1000,
+        &2000,
+        &100,
+        &1000000,
+        &0,
+        &Address::generate(&env),
+    );
+    client.cancel_agreement(&landlord, &draft_id);
+    assert_eq!(
+        client.get_agreement(&draft_id).unwrap().status,
+        AgreementStatus::Cancelled
+    );
+
+    create_pending_agreement(&env, &client, "CANCEL_002", &tenant, &landlord);
+    let pending_id = String::from_str(&env, "CANCEL_002");
+    client.cancel_agreement(&landlord, &pending_id);
+    assert_eq!(
+        client.get_agreement(&pending_id).unwrap().status,
+        AgreementStatus::Cancelled
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_cancel_active_agreement_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+
+    let agreement_id = "CANCEL_003";
+    create_pending_agreement(&env, &client, agreement_id, &tenant, &landlord);
+    client.sign_agreement(&tenant, &String::from_str(&env, agreement_id));
+
+    client.cancel_agreement(&landlord, &String::from_str(&env, agreement_id));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_sign_cancelled_agreement_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+
+    let agreement_id = "CANCEL_004";
+    create_pending_agreement(&env, &client, agreement_id, &tenant, &landlord);
+    client.cancel_agreement(&landlord, &String::from_str(&env, agreement_id));
+
+    client.sign_agreement(&tenant, &String::from_str(&env, agreement_id));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_complete_agreement_before_end_date() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+
+    let agreement_id = "COMPLETE_001";
+    create_pending_agreement(&env, &client, agreement_id, &tenant, &landlord);
+    client.sign_agreement(&tenant, &String::from_str(&env, agreement_id));
+
+    client.complete_agreement(&landlord, &String::from_str(&env, agreement_id));
+}
+
+#[test]
+fn test_complete_agreement_after_end_date() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+
+    let agreement_id = "COMPLETE_002";
+    create_pending_agreement(&env, &client, agreement_id, &tenant, &landlord);
+    client.sign_agreement(&tenant, &String::from_str(&env, agreement_id));
+
+    env.ledger().with_mut(|li| li.timestamp = 1000000);
+    client.complete_agreement(&landlord, &String::from_str(&env, agreement_id));
+
+    let agreement = client
+        .get_agreement(&String::from_str(&env, agreement_id))
+        .unwrap();
+    assert_eq!(agreement.status, AgreementStatus::Completed);
+}
+
+#[test]
+fn test_terminate_active_agreement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+
+    let agreement_id = "TERMINATE_001";
+    create_pending_agreement(&env, &client, agreement_id, &tenant, &landlord);
+    client.sign_agreement(&tenant, &String::from_str(&env, agreement_id));
+
+    client.terminate_agreement(&landlord, &String::from_str(&env, agreement_id));
+
+    let agreement = client
+        .get_agreement(&String::from_str(&env, agreement_id))
+        .unwrap();
+    assert_eq!(agreement.status, AgreementStatus::Terminated);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_terminate_draft_agreement_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let agreement_id = String::from_str(&env, "TERMINATE_002");
+
+    client.create_agreement(
+        &agreement_id,
+        &landlord,
+        &tenant,
+        &None,
+        &1000,
+        &2000,
+        &100,
+        &1000000,
+        &0,
+        &Address::generate(&env),
+    );
+
+    client.terminate_agreement(&landlord, &agreement_id);
+}
+
 #[test]
 fn test_get_agreement() {
     let env = Env::default();
@@ -780,3 +1109,109 @@ proptest! {
         }
     }
 }
+
+// Store an agreement in the legacy layout with `months` payments in its history
+fn store_legacy_agreement(
+    env: &Env,
+    client: &ContractClient,
+    id: &str,
+    months: u32,
+) -> LegacyRentAgreement {
+    let token = Address::generate(env);
+    let tenant = Address::generate(env);
+    let mut history = soroban_sdk::Map::new(env);
+    for month in 0..months {
+        history.set(
+            month,
+            PaymentSplit {
+                landlord_amount: 900,
+                platform_amount: 100,
+                token: token.clone(),
+                payment_date: 100 + month as u64,
+                payer: tenant.clone(),
+            },
+        );
+    }
+    let legacy = LegacyRentAgreement {
+        agreement_id: String::from_str(env, id),
+        landlord: Address::generate(env),
+        tenant,
+        agent: None,
+        monthly_rent: 1000,
+        security_deposit: 2000,
+        start_date: 100,
+        end_date: 1_000_000,
+        agent_commission_rate: 0,
+        status: AgreementStatus::Active,
+        total_rent_paid: 0,
+        payment_count: 0,
+        signed_at: Some(50),
+        payment_token: token,
+        next_payment_due: 100,
+        payment_history: history,
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Agreement(legacy.agreement_id.clone()), &legacy);
+    });
+    legacy
+}
+
+#[test]
+fn test_migrate_agreements_moves_legacy_payment_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    initialize_contract_state(&env, &client, &admin);
+
+    let legacy = store_legacy_agreement(&env, &client, "LEGACY_001", 2);
+    let agreement_id = legacy.agreement_id.clone();
+
+    let ids = soroban_sdk::vec![
+        &env,
+        agreement_id.clone(),
+        String::from_str(&env, "MISSING")
+    ];
+    assert_eq!(client.migrate_agreements(&ids), 1);
+    // Already migrated agreements are skipped
+    assert_eq!(client.migrate_agreements(&ids), 0);
+
+    let agreement = client.get_agreement(&agreement_id).unwrap();
+    assert_eq!(agreement.landlord, legacy.landlord);
+    assert_eq!(agreement.status, AgreementStatus::Active);
+    assert_eq!(agreement.signed_at, Some(50));
+    assert_eq!(agreement.next_payment_due, 100);
+    assert_eq!(agreement.billing_cycle, BillingCycle::CalendarMonth);
+
+    let split = client.get_payment_split(&agreement_id, &1);
+    assert_eq!(split, legacy.payment_history.get_unchecked(1));
+    assert_eq!(
+        client.try_get_payment_split(&agreement_id, &2),
+        Err(Ok(RentalError::PaymentNotFound))
+    );
+}
+
+#[test]
+fn test_agreement_writes_do_not_grow_with_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    initialize_contract_state(&env, &client, &admin);
+
+    let short = store_legacy_agreement(&env, &client, "LEGACY_01", 1);
+    let long = store_legacy_agreement(&env, &client, "LEGACY_24", 24);
+    client.migrate_agreements(&soroban_sdk::vec![
+        &env,
+        short.agreement_id.clone(),
+        long.agreement_id.clone()
+    ]);
+
+    client.terminate_agreement(&short.landlord, &short.agreement_id);
+    let short_writes = env.cost_estimate().resources().write_bytes;
+    client.terminate_agreement(&long.landlord, &long.agreement_id);
+    let long_writes = env.cost_estimate().resources().write_bytes;
+    assert_eq!(short_writes, long_writes);
+}
@@ -15,6 +15,39 @@ pub enum AgreementStatus {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RentAgreement {
+    pub agreement_id: String,
+    pub landlord: Address,
+    pub tenant: Address,
+    pub agent: Option<Address>,
+    pub monthly_rent: i128,
+    pub security_deposit: i128,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub agent_commission_rate: u32,
+    pub status: AgreementStatus,
+    pub total_rent_paid: i128,
+    pub payment_count: u32,
+    pub signed_at: Option<u64>,
+    pub payment_token: Address,
+    pub next_payment_due: u64,
+    pub billing_cycle: BillingCycle,
+}
+
+/// How an agreement's due dates are spaced from its start date
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BillingCycle {
+    /// Same day of month as the start date, clamped to the last day of short months
+    CalendarMonth,
+    /// Fixed-length periods of the given number of seconds
+    FixedPeriod(u64),
+}
+
+/// Agreement layout that embedded every payment split, kept only so
+/// `migrate_agreements` can read agreements stored before the split entries moved out.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRentAgreement {
     pub agreement_id: String,
     pub landlord: Address,
     pub tenant: Address,
@@ -33,6 +66,7 @@ pub struct RentAgreement {
     pub payment_history: Map<u32, PaymentSplit>,
 }
 
+/// Split of one month's rent payment, stored in its own entry per agreement and month
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PaymentSplit {
@@ -4,7 +4,8 @@
 use soroban_sdk::{Address, Env};
 
 use crate::errors::EscrowError;
-use crate::types::Escrow;
+use crate::storage::EscrowStorage;
+use crate::types::{Escrow, SignerSet};
 
 /// Access control validation functions.
 pub struct AccessControl;
@@ -47,6 +48,16 @@ impl AccessControl {
         }
     }
 
+    /// Verify caller is in the escrow's signer set and return its weight.
+    pub fn is_signer(signer_set: &SignerSet, caller: &Address) -> Result<u32, EscrowError> {
+        signer_set
+            .signers
+            .iter()
+            .find(|signer| signer.address == *caller)
+            .map(|signer| signer.weight)
+            .ok_or(EscrowError::InvalidSigner)
+    }
+
     /// Verify caller is either depositor or beneficiary (the two primary parties).
     /// Used for dispute initiation.
     pub fn is_primary_party(escrow: &Escrow, caller: &Address) -> Result<(), EscrowError> {
@@ -56,4 +67,15 @@ impl AccessControl {
             Err(EscrowError::NotAuthorized)
         }
     }
+
+    /// Verify caller is the contract admin.
+    /// Fails with NotInitialized if no admin has been set.
+    pub fn is_admin(env: &Env, caller: &Address) -> Result<(), EscrowError> {
+        let state = EscrowStorage::get_state(env).ok_or(EscrowError::NotInitialized)?;
+        if state.admin == *caller {
+            Ok(())
+        } else {
+            Err(EscrowError::NotAuthorized)
+        }
+    }
 }
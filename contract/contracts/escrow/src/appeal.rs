@@ -0,0 +1,282 @@
+//! Appeals of arbiter rulings for the Escrow contract.
+//! On escrows with an appeal arbiter, an arbiter ruling is provisional: either
+//! primary party may appeal within the appeal window by posting a bond, and the
+//! appeal arbiter's decision is final. Funds move only once the window closes
+//! unappealed or the appeal is decided. An appeal arbiter who does not decide
+//! within the ruling window lets the provisional ruling stand.
+use soroban_sdk::{Address, BytesN, Env};
+
+use crate::access::AccessControl;
+use crate::custody::Custody;
+use crate::dispute::DisputeHandler;
+use crate::errors::EscrowError;
+use crate::escrow_impl::EscrowHandler;
+use crate::events;
+use crate::registry::ArbiterRegistry;
+use crate::storage::EscrowStorage;
+use crate::types::{Appeal, Escrow, EscrowStatus, ProvisionalRuling, ReleaseSplit, ResolutionPath};
+
+/// Appeal handling for provisional rulings.
+pub struct AppealHandler;
+
+impl AppealHandler {
+    /// Name a second-tier arbiter for a Pending escrow, making arbiter rulings appealable.
+    /// Both primary parties must agree on who decides their appeals.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the beneficiary
+    /// - Depositor must authorize
+    /// - Escrow must be in Pending state
+    /// - Appeal arbiter must not be a party to the escrow
+    pub fn set_appeal_arbiter(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        appeal_arbiter: &Address,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_beneficiary(&escrow, caller)?;
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Pending {
+            return Err(EscrowError::InvalidState);
+        }
+
+        if AccessControl::is_party(&escrow, appeal_arbiter).is_ok() {
+            return Err(EscrowError::InvalidSigner);
+        }
+
+        // EFFECTS: Store the appeal arbiter
+        EscrowStorage::set_appeal_arbiter(env, escrow_id, appeal_arbiter);
+
+        Ok(())
+    }
+
+    /// Record an arbiter ruling as provisional, open to appeal until now + appeal window.
+    pub fn record_ruling(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        split: ReleaseSplit,
+        path: ResolutionPath,
+    ) {
+        let now = env.ledger().timestamp();
+        let ruling = ProvisionalRuling {
+            split,
+            path,
+            ruled_at: now,
+            appeal_deadline: now + EscrowStorage::get_config(env).appeal_window,
+        };
+        EscrowStorage::save_ruling(env, escrow_id, &ruling);
+    }
+
+    /// Appeal a provisional ruling by posting a bond in the escrow token.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be depositor or beneficiary
+    /// - Escrow must be Disputed with a provisional ruling
+    /// - Appeal window must not have closed
+    /// - Ruling must not already be appealed
+    ///
+    /// EFFECTS:
+    /// - Store the appeal; the appeal arbiter now decides the dispute within the
+    ///   dispute ruling window
+    ///
+    /// INTERACTIONS:
+    /// - Transfer the bond (appeal_bond_bps of the disputed amount) into the contract
+    pub fn appeal_ruling(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_primary_party(&escrow, caller)?;
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let ruling = EscrowStorage::get_ruling(env, escrow_id).ok_or(EscrowError::NoRuling)?;
+        if env.ledger().timestamp() > ruling.appeal_deadline {
+            return Err(EscrowError::AppealWindowClosed);
+        }
+        if EscrowStorage::get_appeal(env, escrow_id).is_some() {
+            return Err(EscrowError::AlreadyAppealed);
+        }
+
+        // EFFECTS: Store the appeal
+        let config = EscrowStorage::get_config(env);
+        let disputed = EscrowHandler::current_amount(env, &escrow);
+        let bond = disputed * config.appeal_bond_bps as i128 / 10_000;
+        let now = env.ledger().timestamp();
+        let appeal = Appeal {
+            appellant: caller.clone(),
+            bond,
+            filed_at: now,
+            decide_by: now + config.dispute_ruling_window,
+        };
+        EscrowStorage::save_appeal(env, escrow_id, &appeal);
+
+        // INTERACTIONS: Take the bond into custody
+        if bond > 0 {
+            Custody::deposit(env, &escrow.token, caller, bond)?;
+        }
+
+        Ok(())
+    }
+
+    /// Settle a provisional ruling nobody appealed, or whose appeal the appeal arbiter
+    /// did not decide in time. Anyone may call this once the relevant window has closed.
+    ///
+    /// CHECKS:
+    /// - Escrow must be Disputed with a provisional ruling
+    /// - Appeal window must have closed without an appeal, or the appeal's
+    ///   decision deadline must have passed
+    ///
+    /// EFFECTS:
+    /// - Clear dispute record and settle the escrow with the ruled split
+    ///
+    /// INTERACTIONS:
+    /// - Return an undecided appeal's bond to the appellant
+    pub fn finalize_ruling(env: &Env, escrow_id: &BytesN<32>) -> Result<(), EscrowError> {
+        let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let ruling = EscrowStorage::get_ruling(env, escrow_id).ok_or(EscrowError::NoRuling)?;
+        let now = env.ledger().timestamp();
+        let appeal = EscrowStorage::get_appeal(env, escrow_id);
+        match &appeal {
+            Some(appeal) if now <= appeal.decide_by => return Err(EscrowError::RulingWindowOpen),
+            None if now <= ruling.appeal_deadline => return Err(EscrowError::AppealWindowOpen),
+            _ => {}
+        }
+
+        // EFFECTS: Clear dispute, then settle
+        DisputeHandler::close(env, &escrow);
+        EscrowStorage::clear_offer(env, escrow_id);
+        EscrowHandler::settle(env, &mut escrow, ruling.split, ruling.path)?;
+
+        // INTERACTIONS: The appellant is not penalized for an undecided appeal
+        if let Some(appeal) = appeal {
+            if appeal.bond > 0 {
+                Custody::pay_out(env, &escrow.token, &appeal.appellant, appeal.bond)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decide an appealed ruling (appeal arbiter only). The decision is final.
+    /// The appellant wins if the decision gives it more than the provisional ruling
+    /// and gets its bond back; otherwise the bond goes to the other primary party.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the escrow's appeal arbiter
+    /// - Escrow must be Disputed with an appealed ruling
+    /// - Appeal's decision deadline must not have passed
+    /// - Split must be non-negative and add up to the escrowed amount
+    /// - Split must pay the escrow's arbiter exactly the registry fee when appealing
+    ///   its ruling, and nothing when appealing a panel's
+    ///
+    /// EFFECTS:
+    /// - Count the appeal (and any reversal) against the escrow arbiter
+    /// - Clear dispute record and settle the escrow via the Appeal path
+    ///
+    /// INTERACTIONS:
+    /// - Pay out the split, then return or forfeit the bond
+    pub fn decide_appeal(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+
+        let appeal_arbiter =
+            EscrowStorage::get_appeal_arbiter(env, escrow_id).ok_or(EscrowError::NotAuthorized)?;
+        if appeal_arbiter != *caller {
+            return Err(EscrowError::NotAuthorized);
+        }
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let ruling = EscrowStorage::get_ruling(env, escrow_id).ok_or(EscrowError::NoRuling)?;
+        let appeal = EscrowStorage::get_appeal(env, escrow_id).ok_or(EscrowError::NoAppeal)?;
+        if env.ledger().timestamp() > appeal.decide_by {
+            return Err(EscrowError::AppealWindowClosed);
+        }
+
+        EscrowHandler::validate_split(env, &escrow, &split)?;
+        if ruling.path == ResolutionPath::Arbiter {
+            ArbiterRegistry::check_fee(env, &escrow, &split)?;
+        } else if split.arbiter_fee != 0 {
+            return Err(EscrowError::InvalidArbiterFee);
+        }
+
+        let bond_recipient = if Self::appellant_won(&escrow, &appeal, &ruling.split, &split) {
+            appeal.appellant.clone()
+        } else if appeal.appellant == escrow.depositor {
+            escrow.beneficiary.clone()
+        } else {
+            escrow.depositor.clone()
+        };
+
+        // EFFECTS: Update the arbiter's appeal record, clear dispute, then settle
+        if ruling.path == ResolutionPath::Arbiter {
+            ArbiterRegistry::record_appeal(env, &escrow.arbiter, split != ruling.split);
+        }
+        DisputeHandler::close(env, &escrow);
+        EscrowStorage::clear_offer(env, escrow_id);
+        EscrowHandler::settle(env, &mut escrow, split.clone(), ResolutionPath::Appeal)?;
+        events::dispute_resolved(env, &escrow, caller, &split, &ResolutionPath::Appeal);
+
+        // INTERACTIONS: Return or forfeit the bond
+        if appeal.bond > 0 {
+            Custody::pay_out(env, &escrow.token, &bond_recipient, appeal.bond)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the appeal decision gives the appellant more than the provisional ruling did.
+    fn appellant_won(
+        escrow: &Escrow,
+        appeal: &Appeal,
+        ruled: &ReleaseSplit,
+        decided: &ReleaseSplit,
+    ) -> bool {
+        if appeal.appellant == escrow.depositor {
+            decided.depositor_amount > ruled.depositor_amount
+        } else {
+            decided.beneficiary_amount > ruled.beneficiary_amount
+        }
+    }
+
+    /// Get the provisional ruling on an escrow's dispute, if any.
+    pub fn get_ruling(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+    ) -> Result<Option<ProvisionalRuling>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_ruling(env, escrow_id))
+    }
+
+    /// Get the appeal filed against an escrow's provisional ruling, if any.
+    pub fn get_appeal(env: &Env, escrow_id: &BytesN<32>) -> Result<Option<Appeal>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_appeal(env, escrow_id))
+    }
+}
@@ -0,0 +1,56 @@
+//! Token custody for the Escrow contract.
+//! Moves escrowed tokens in and out of the contract and tracks the total held
+//! per token so the contract balance can be checked against open escrows.
+use soroban_sdk::{token, Address, Env};
+
+use crate::errors::EscrowError;
+use crate::storage::EscrowStorage;
+
+/// Token transfers into and out of escrow custody.
+pub struct Custody;
+
+impl Custody {
+    /// Pull `amount` of `token` from `from` into the contract.
+    /// `from` must have authorized the enclosing invocation.
+    pub fn deposit(
+        env: &Env,
+        token: &Address,
+        from: &Address,
+        amount: i128,
+    ) -> Result<(), EscrowError> {
+        EscrowStorage::add_total_held(env, token, amount);
+
+        let contract = env.current_contract_address();
+        token::Client::new(env, token).transfer(from, &contract, &amount);
+
+        Self::verify_balance(env, token)
+    }
+
+    /// Pay `amount` of `token` held by the contract out to `to`.
+    pub fn pay_out(
+        env: &Env,
+        token: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), EscrowError> {
+        Self::verify_balance(env, token)?;
+        EscrowStorage::sub_total_held(env, token, amount);
+
+        let contract = env.current_contract_address();
+        token::Client::new(env, token).transfer(&contract, to, &amount);
+
+        Ok(())
+    }
+
+    /// Verify the contract holds at least the sum of the escrows it custodies.
+    pub fn verify_balance(env: &Env, token: &Address) -> Result<(), EscrowError> {
+        let contract = env.current_contract_address();
+        let balance = token::Client::new(env, token).balance(&contract);
+
+        if balance < EscrowStorage::get_total_held(env, token) {
+            return Err(EscrowError::BalanceMismatch);
+        }
+
+        Ok(())
+    }
+}
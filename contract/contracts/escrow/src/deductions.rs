@@ -0,0 +1,301 @@
+//! Itemized deposit deduction claims for the Escrow contract.
+//! The beneficiary files claim lines at lease end; the depositor accepts all,
+//! some or none of them within a response window. Contested lines go to dispute
+//! while the uncontested part is released right away.
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use crate::access::AccessControl;
+use crate::custody::Custody;
+use crate::dispute::DisputeHandler;
+use crate::errors::EscrowError;
+use crate::escrow_impl::EscrowHandler;
+use crate::events;
+use crate::storage::EscrowStorage;
+use crate::types::{
+    ClaimStatus, DeductionClaim, DeductionItem, DisputeCategory, DisputeReason, Escrow,
+    EscrowStatus, ReleaseSplit, ResolutionPath, Settlement,
+};
+use crate::vault::YieldVault;
+
+/// Time the depositor has to respond to a deduction claim (14 days).
+pub const CLAIM_RESPONSE_WINDOW: u64 = 1_209_600;
+
+/// Deduction claim handling.
+pub struct DeductionHandler;
+
+impl DeductionHandler {
+    /// File an itemized deduction claim against a Funded escrow.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the beneficiary
+    /// - Escrow must be in Funded state and not released in milestones
+    /// - No claim may already exist for the escrow
+    /// - Claim must have at least one line, every line positive, total within the escrow
+    ///
+    /// EFFECTS:
+    /// - Store the claim with a response deadline of now + CLAIM_RESPONSE_WINDOW
+    pub fn file_claim(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        items: Vec<DeductionItem>,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_beneficiary(&escrow, caller)?;
+
+        // Milestone escrows are settled milestone by milestone instead
+        if escrow.status != EscrowStatus::Funded
+            || EscrowStorage::get_milestones(env, escrow_id).is_some()
+        {
+            return Err(EscrowError::InvalidState);
+        }
+
+        if EscrowStorage::get_claim(env, escrow_id).is_some() {
+            return Err(EscrowError::ClaimAlreadyFiled);
+        }
+
+        if items.is_empty() {
+            return Err(EscrowError::InvalidClaim);
+        }
+
+        let mut total: i128 = 0;
+        for item in &items {
+            if item.amount <= 0 {
+                return Err(EscrowError::InvalidClaim);
+            }
+            total = total
+                .checked_add(item.amount)
+                .ok_or(EscrowError::InvalidClaim)?;
+        }
+        if total > escrow.funded {
+            return Err(EscrowError::InvalidClaim);
+        }
+
+        // EFFECTS: Store the claim
+        let now = env.ledger().timestamp();
+        let claim = DeductionClaim {
+            items,
+            accepted_lines: Vec::new(env),
+            status: ClaimStatus::Open,
+            filed_at: now,
+            response_deadline: now + CLAIM_RESPONSE_WINDOW,
+        };
+        EscrowStorage::save_claim(env, escrow_id, &claim);
+
+        Ok(())
+    }
+
+    /// Respond to an open deduction claim as the depositor.
+    /// `accepted_lines` lists the indices of the claim lines the depositor accepts;
+    /// every other line is contested.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the depositor
+    /// - Escrow must be in Funded state with an Open claim
+    /// - Response window must not have closed
+    /// - Accepted line indices must be in range and unique
+    ///
+    /// EFFECTS:
+    /// - Nothing contested: settle the escrow (accepted total to beneficiary, rest to depositor)
+    /// - Otherwise: release accepted lines and the unclaimed remainder now, then
+    ///   dispute the contested lines with the remaining escrowed amount
+    pub fn respond_to_claim(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        accepted_lines: Vec<u32>,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_depositor(&escrow, caller)?;
+
+        if escrow.status != EscrowStatus::Funded {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let mut claim = Self::get_open_claim(env, escrow_id)?;
+        if env.ledger().timestamp() > claim.response_deadline {
+            return Err(EscrowError::ClaimWindowClosed);
+        }
+
+        let item_count = claim.items.len();
+        for (position, line) in accepted_lines.iter().enumerate() {
+            if line >= item_count {
+                return Err(EscrowError::InvalidClaim);
+            }
+            if accepted_lines.first_index_of(line) != Some(position as u32) {
+                return Err(EscrowError::InvalidClaim);
+            }
+        }
+
+        claim.accepted_lines = accepted_lines;
+        Self::apply_response(env, &mut escrow, claim)
+    }
+
+    /// Settle a claim the depositor did not answer in time; every line is treated as accepted.
+    /// Anyone may call this once the response window has closed.
+    pub fn settle_unanswered_claim(env: &Env, escrow_id: &BytesN<32>) -> Result<(), EscrowError> {
+        let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+
+        if escrow.status != EscrowStatus::Funded {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let mut claim = Self::get_open_claim(env, escrow_id)?;
+        if env.ledger().timestamp() <= claim.response_deadline {
+            return Err(EscrowError::ClaimWindowOpen);
+        }
+
+        let mut all_lines = Vec::new(env);
+        for line in 0..claim.items.len() {
+            all_lines.push_back(line);
+        }
+        claim.accepted_lines = all_lines;
+
+        Self::apply_response(env, &mut escrow, claim)
+    }
+
+    /// Get the uncontested part of a deduction claim released ahead of the dispute
+    /// over the contested lines, if any. The later settlement covers only the
+    /// contested amount.
+    pub fn get_partial_release(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+    ) -> Result<Option<Settlement>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_partial_release(env, escrow_id))
+    }
+
+    /// Get the deduction claim filed against an escrow, if any.
+    pub fn get_claim(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+    ) -> Result<Option<DeductionClaim>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_claim(env, escrow_id))
+    }
+
+    /// Put the contested lines of a claim back before the depositor after it withdrew
+    /// the dispute over them. The lines already accepted were paid out, so only the
+    /// contested ones stay on the claim; the original response deadline still applies.
+    pub fn reopen_contested(env: &Env, escrow_id: &BytesN<32>, contested_lines: &Vec<u32>) {
+        let Some(mut claim) = EscrowStorage::get_claim(env, escrow_id) else {
+            return;
+        };
+
+        let mut items = Vec::new(env);
+        for line in contested_lines.iter() {
+            if let Some(item) = claim.items.get(line) {
+                items.push_back(item);
+            }
+        }
+        claim.items = items;
+        claim.accepted_lines = Vec::new(env);
+        claim.status = ClaimStatus::Open;
+        EscrowStorage::save_claim(env, escrow_id, &claim);
+    }
+
+    fn get_open_claim(env: &Env, escrow_id: &BytesN<32>) -> Result<DeductionClaim, EscrowError> {
+        let claim = EscrowStorage::get_claim(env, escrow_id).ok_or(EscrowError::ClaimNotFound)?;
+        if claim.status != ClaimStatus::Open {
+            return Err(EscrowError::ClaimNotOpen);
+        }
+        Ok(claim)
+    }
+
+    /// Split the escrow according to the claim lines the depositor accepted.
+    fn apply_response(
+        env: &Env,
+        escrow: &mut Escrow,
+        mut claim: DeductionClaim,
+    ) -> Result<(), EscrowError> {
+        let mut accepted_total: i128 = 0;
+        let mut contested_total: i128 = 0;
+        let mut contested_lines = Vec::new(env);
+        for (line, item) in claim.items.iter().enumerate() {
+            let line = line as u32;
+            if claim.accepted_lines.contains(line) {
+                accepted_total += item.amount;
+            } else {
+                contested_total += item.amount;
+                contested_lines.push_back(line);
+            }
+        }
+        let mut unclaimed = escrow.funded - accepted_total - contested_total;
+
+        if contested_lines.is_empty() {
+            claim.status = ClaimStatus::Accepted;
+            EscrowStorage::save_claim(env, &escrow.id, &claim);
+
+            let split = ReleaseSplit {
+                beneficiary_amount: accepted_total,
+                depositor_amount: unclaimed,
+                arbiter_fee: 0,
+            };
+            return EscrowHandler::settle(env, escrow, split, ResolutionPath::DeductionClaim);
+        }
+
+        // INTERACTIONS: Bring the funds back from the yield vault before the
+        // escrowed amount shrinks, so the yield is shared on the full deposit;
+        // a vault loss is shared pro rata across every part of the claim
+        let loss = YieldVault::recall(env, escrow)?;
+        if loss > 0 {
+            let mut parts = [contested_total, accepted_total, unclaimed];
+            YieldVault::share_loss(&mut parts, escrow.funded, loss);
+            [contested_total, accepted_total, unclaimed] = parts;
+        }
+
+        // EFFECTS: Keep only the contested amount in escrow and dispute it
+        claim.status = if accepted_total > 0 {
+            ClaimStatus::PartiallyAccepted
+        } else {
+            ClaimStatus::Contested
+        };
+        EscrowStorage::save_claim(env, &escrow.id, &claim);
+
+        let release = Settlement {
+            split: ReleaseSplit {
+                beneficiary_amount: accepted_total,
+                depositor_amount: unclaimed,
+                arbiter_fee: 0,
+            },
+            path: ResolutionPath::DeductionClaim,
+            settled_at: env.ledger().timestamp(),
+        };
+        // A claim reopened by a withdrawn dispute adds to the earlier release
+        let mut recorded = release.clone();
+        if let Some(earlier) = EscrowStorage::get_partial_release(env, &escrow.id) {
+            recorded.split.beneficiary_amount += earlier.split.beneficiary_amount;
+            recorded.split.depositor_amount += earlier.split.depositor_amount;
+        }
+        EscrowStorage::save_partial_release(env, &escrow.id, &recorded);
+
+        escrow.amount = contested_total;
+        escrow.funded = contested_total;
+        let depositor = escrow.depositor.clone();
+        DisputeHandler::open(
+            env,
+            escrow,
+            &depositor,
+            DisputeCategory::ContestedDeductions,
+            DisputeReason::ContestedDeductions(contested_lines),
+        )?;
+
+        // INTERACTIONS: Release the uncontested part right away
+        if accepted_total > 0 {
+            Custody::pay_out(env, &escrow.token, &escrow.beneficiary, accepted_total)?;
+        }
+        if unclaimed > 0 {
+            Custody::pay_out(env, &escrow.token, &escrow.depositor, unclaimed)?;
+        }
+
+        events::partial_release(env, escrow, &release);
+
+        Ok(())
+    }
+}
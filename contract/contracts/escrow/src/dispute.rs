@@ -1,11 +1,26 @@
 //! Dispute resolution and admin override for the Escrow contract.
-//! Allows either party to freeze funds and requires admin to resolve.
-use soroban_sdk::{Address, BytesN, Env, String};
+//! Allows either party to freeze funds; the escrow's arbiter or panel rules, and
+//! the admin only once the dispute has been escalated.
+//! Each dispute keeps an append-only evidence log and two deadlines: the
+//! counter-party response window and the arbiter ruling window.
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
 use crate::access::AccessControl;
+use crate::appeal::AppealHandler;
+use crate::deductions::DeductionHandler;
 use crate::errors::EscrowError;
+use crate::escrow_impl::EscrowHandler;
+use crate::events;
+use crate::panel::PanelHandler;
+use crate::registry::ArbiterRegistry;
 use crate::storage::EscrowStorage;
-use crate::types::EscrowStatus;
+use crate::types::{
+    Dispute, DisputeCategory, DisputeReason, Escrow, EscrowStatus, Evidence, ReleaseSplit,
+    ResolutionPath, SettlementOffer,
+};
+
+/// Maximum number of evidence entries kept per dispute.
+pub const MAX_EVIDENCE: u32 = 20;
 
 /// Dispute handling and resolution.
 pub struct DisputeHandler;
@@ -15,6 +30,7 @@ impl DisputeHandler {
     /// Either depositor or beneficiary can call this.
     ///
     /// CHECKS:
+    /// - Caller must authorize
     /// - Escrow must exist
     /// - Escrow must be in Funded state
     /// - Caller must be depositor or beneficiary
@@ -22,15 +38,19 @@ impl DisputeHandler {
     ///
     /// EFFECTS:
     /// - Update escrow status to Disputed
-    /// - Store dispute reason
+    /// - Store dispute record with response and ruling deadlines
     /// - Clear existing approvals (freeze funds)
     pub fn initiate_dispute(
         env: &Env,
         escrow_id: &BytesN<32>,
         caller: &Address,
+        category: DisputeCategory,
         reason: String,
     ) -> Result<(), EscrowError> {
-        // CHECKS: Get and validate escrow
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        // Get and validate escrow
         let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
 
         // Verify caller is a primary party (depositor or beneficiary)
@@ -46,78 +66,457 @@ impl DisputeHandler {
             return Err(EscrowError::EmptyDisputeReason);
         }
 
-        // EFFECTS: Update status and store reason
+        // EFFECTS: Update status and store dispute record
+        Self::open(
+            env,
+            &mut escrow,
+            caller,
+            category,
+            DisputeReason::Statement(reason),
+        )
+    }
+
+    /// Move an escrow into Disputed and freeze it.
+    ///
+    /// EFFECTS:
+    /// - Update escrow status to Disputed
+    /// - Store dispute record under DataKey::DisputeInfo, with deadlines from the config
+    /// - Draw an arbiter panel if the escrow asked for one
+    /// - Count the dispute against the arbiter or each panelist deciding it
+    /// - Clear existing approvals (freeze funds)
+    pub fn open(
+        env: &Env,
+        escrow: &mut Escrow,
+        opened_by: &Address,
+        category: DisputeCategory,
+        reason: DisputeReason,
+    ) -> Result<(), EscrowError> {
+        let config = EscrowStorage::get_config(env);
+        let now = env.ledger().timestamp();
+        let response_deadline = now + config.dispute_response_window;
+
+        let dispute = Dispute {
+            opened_by: opened_by.clone(),
+            opened_at: now,
+            category,
+            reason,
+            evidence: Vec::new(env),
+            response_deadline,
+            ruling_deadline: response_deadline + config.dispute_ruling_window,
+            escalated: false,
+        };
+
         escrow.status = EscrowStatus::Disputed;
-        escrow.dispute_reason = Some(reason);
-        EscrowStorage::save(env, &escrow);
+        EscrowStorage::save(env, escrow);
+        EscrowStorage::save_dispute(env, &escrow.id, &dispute);
+        PanelHandler::assign_panel(env, escrow, &dispute)?;
+        for arbiter in Self::deciders(env, escrow) {
+            ArbiterRegistry::assign(env, &arbiter);
+        }
 
         // Freeze funds by clearing all approvals
-        EscrowStorage::clear_approvals(env, escrow_id);
+        EscrowStorage::clear_approvals(env, &escrow.id);
+
+        let amount = EscrowHandler::current_amount(env, escrow);
+        events::dispute_opened(env, escrow, opened_by, category, amount, response_deadline);
+
+        Ok(())
+    }
+
+    /// Append evidence to an open dispute.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be depositor or beneficiary
+    /// - Escrow must be in Disputed state
+    /// - Dispute must not already hold MAX_EVIDENCE entries
+    ///
+    /// EFFECTS:
+    /// - Append the evidence entry; existing entries are never modified
+    pub fn submit_evidence(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        content_hash: BytesN<32>,
+        uri: String,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_primary_party(&escrow, caller)?;
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let mut dispute =
+            EscrowStorage::get_dispute(env, escrow_id).ok_or(EscrowError::InvalidState)?;
+        if dispute.evidence.len() >= MAX_EVIDENCE {
+            return Err(EscrowError::EvidenceLimitReached);
+        }
+
+        // EFFECTS: Append evidence
+        dispute.evidence.push_back(Evidence {
+            submitter: caller.clone(),
+            content_hash: content_hash.clone(),
+            uri,
+            submitted_at: env.ledger().timestamp(),
+        });
+        EscrowStorage::save_dispute(env, escrow_id, &dispute);
+        events::evidence_added(env, &escrow, caller, &content_hash, dispute.evidence.len());
+
+        Ok(())
+    }
+
+    /// Escalate a dispute the arbiter has not ruled on by its ruling deadline.
+    /// Once escalated, only the contract admin can resolve it.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be depositor or beneficiary
+    /// - Escrow must be in Disputed state and not already escalated
+    /// - Ruling deadline must have passed
+    pub fn escalate_dispute(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_primary_party(&escrow, caller)?;
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let mut dispute =
+            EscrowStorage::get_dispute(env, escrow_id).ok_or(EscrowError::InvalidState)?;
+        if dispute.escalated {
+            return Err(EscrowError::InvalidState);
+        }
+        if EscrowStorage::get_ruling(env, escrow_id).is_some() {
+            return Err(EscrowError::RulingPending);
+        }
+        if env.ledger().timestamp() <= dispute.ruling_deadline {
+            return Err(EscrowError::RulingWindowOpen);
+        }
+
+        // EFFECTS: Hand the dispute to the admin
+        dispute.escalated = true;
+        EscrowStorage::save_dispute(env, escrow_id, &dispute);
 
         Ok(())
     }
 
-    /// Resolve a dispute (admin only).
-    /// Admin can release funds to either party or refund to depositor.
+    /// Resolve a dispute as the escrow's arbiter, or as the admin once it is escalated.
+    /// The split divides the amount still held between beneficiary and depositor;
+    /// an arbiter ruling also pays the arbiter its registry fee.
     ///
     /// CHECKS:
+    /// - Caller must authorize
     /// - Escrow must exist
     /// - Escrow must be in Disputed state
-    /// - Caller must be arbiter
-    /// - Release target must be beneficiary or depositor
+    /// - Caller must be arbiter, or the contract admin once the dispute is escalated
+    /// - Disputes assigned to a panel are decided by panel vote, not the arbiter
+    /// - Counter-party must have submitted evidence or had its full response window
+    /// - No provisional ruling may already be pending
+    /// - Split must be non-negative and add up to the escrowed amount
+    /// - Arbiter rulings must pay exactly the registry fee to the arbiter
     ///
     /// EFFECTS:
-    /// - Update escrow status to Released or Refunded
-    /// - Clear dispute reason
-    /// - Clear approvals
+    /// - Count the ruling in the arbiter's registry statistics
+    /// - Escrow with an appeal arbiter: record a provisional ruling open to appeal
+    /// - Otherwise clear dispute record and settle the escrow:
+    ///   Refunded if nothing goes to the beneficiary, Released otherwise
     ///
     /// INTERACTIONS:
-    /// - Token transfer would happen after state update
+    /// - Transfer each share of the amount up for release (what is left after any
+    ///   partial release, or the current milestone's) once settled; the deposit is
+    ///   recalled from its yield vault first and any vault loss shared pro rata
     pub fn resolve_dispute(
         env: &Env,
         escrow_id: &BytesN<32>,
         caller: &Address,
-        release_to: Address,
+        split: ReleaseSplit,
     ) -> Result<(), EscrowError> {
-        // CHECKS: Get and validate escrow
-        let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        // CHECKS: Caller must authorize
+        caller.require_auth();
 
-        // Verify caller is arbiter
-        AccessControl::is_arbiter(&escrow, caller)?;
+        // Get and validate escrow
+        let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
 
         // Verify escrow is in Disputed state
         if escrow.status != EscrowStatus::Disputed {
             return Err(EscrowError::InvalidState);
         }
 
-        // Verify release target is valid
-        if release_to != escrow.beneficiary && release_to != escrow.depositor {
-            return Err(EscrowError::InvalidApprovalTarget);
+        let dispute =
+            EscrowStorage::get_dispute(env, escrow_id).ok_or(EscrowError::InvalidState)?;
+
+        // Verify caller is arbiter, or the admin for an escalated dispute
+        let path = if dispute.escalated {
+            AccessControl::is_admin(env, caller)?;
+            ResolutionPath::Admin
+        } else {
+            AccessControl::is_arbiter(&escrow, caller)?;
+            if EscrowStorage::get_panel(env, escrow_id).is_some() {
+                return Err(EscrowError::NotAuthorized);
+            }
+            ResolutionPath::Arbiter
+        };
+
+        Self::check_ruling_allowed(env, &escrow, &dispute)?;
+
+        // Verify split accounts for exactly the escrowed amount
+        EscrowHandler::validate_split(env, &escrow, &split)?;
+        if path == ResolutionPath::Arbiter {
+            ArbiterRegistry::check_fee(env, &escrow, &split)?;
         }
 
-        // EFFECTS: Update status and clear dispute
-        escrow.status = EscrowStatus::Released;
-        escrow.dispute_reason = None;
-        EscrowStorage::save(env, &escrow);
+        // EFFECTS: Record or apply the ruling
+        if path == ResolutionPath::Arbiter {
+            ArbiterRegistry::record_ruling(env, &escrow.arbiter);
+        }
+        Self::apply_ruling(env, &mut escrow, caller, split, path)
+    }
 
-        // Clear approvals
+    /// Verify a ruling may be made now: the counter-party has responded or had its full
+    /// response window, and no provisional ruling is already pending.
+    pub fn check_ruling_allowed(
+        env: &Env,
+        escrow: &Escrow,
+        dispute: &Dispute,
+    ) -> Result<(), EscrowError> {
+        // Counter-party gets its response window unless it has already responded
+        if env.ledger().timestamp() < dispute.response_deadline
+            && !Self::counter_party_responded(escrow, dispute)
+        {
+            return Err(EscrowError::ResponseWindowOpen);
+        }
+
+        if EscrowStorage::get_ruling(env, &escrow.id).is_some() {
+            return Err(EscrowError::RulingPending);
+        }
+
+        Ok(())
+    }
+
+    /// Clear an escrow's dispute record, releasing the arbiter or panelists that were
+    /// deciding it. Does nothing once the record is gone.
+    pub fn close(env: &Env, escrow: &Escrow) {
+        if EscrowStorage::get_dispute(env, &escrow.id).is_none() {
+            return;
+        }
+        for arbiter in Self::deciders(env, escrow) {
+            ArbiterRegistry::unassign(env, &arbiter);
+        }
+        EscrowStorage::clear_dispute(env, &escrow.id);
+    }
+
+    /// Arbiters deciding the escrow's dispute: its panel, or else the escrow arbiter.
+    fn deciders(env: &Env, escrow: &Escrow) -> Vec<Address> {
+        match EscrowStorage::get_panel(env, &escrow.id) {
+            Some(panel) => panel.members,
+            None => Vec::from_array(env, [escrow.arbiter.clone()]),
+        }
+    }
+
+    /// Apply a validated ruling. Arbiter and panel rulings on escrows with an appeal
+    /// arbiter become provisional; every other ruling clears the dispute and settles.
+    pub fn apply_ruling(
+        env: &Env,
+        escrow: &mut Escrow,
+        resolved_by: &Address,
+        split: ReleaseSplit,
+        path: ResolutionPath,
+    ) -> Result<(), EscrowError> {
+        // Rulings on appealable escrows wait out the appeal window
+        if path != ResolutionPath::Admin
+            && EscrowStorage::get_appeal_arbiter(env, &escrow.id).is_some()
+        {
+            events::dispute_resolved(env, escrow, resolved_by, &split, &path);
+            AppealHandler::record_ruling(env, &escrow.id, split, path);
+            return Ok(());
+        }
+
+        // Clear dispute, then settle
+        Self::close(env, escrow);
+        EscrowStorage::clear_offer(env, &escrow.id);
+        EscrowHandler::settle(env, escrow, split.clone(), path.clone())?;
+        events::dispute_resolved(env, escrow, resolved_by, &split, &path);
+
+        Ok(())
+    }
+
+    /// Propose a split to settle a dispute without the arbiter.
+    /// A new proposal from either party replaces the open one.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be depositor or beneficiary
+    /// - Escrow must be Disputed with no provisional ruling pending
+    /// - Split must be non-negative and add up to the escrowed amount
+    ///
+    /// EFFECTS:
+    /// - Store the offer for the other party to accept
+    pub fn propose_settlement(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = Self::get_settleable(env, escrow_id, caller)?;
+        EscrowHandler::validate_split(env, &escrow, &split)?;
+
+        // EFFECTS: Store the offer
+        let offer = SettlementOffer {
+            proposer: caller.clone(),
+            split,
+            proposed_at: env.ledger().timestamp(),
+        };
+        EscrowStorage::save_offer(env, escrow_id, &offer);
+
+        Ok(())
+    }
+
+    /// Accept the other party's settlement offer and pay out with no arbiter involved.
+    /// `split` must repeat the offered split so a replaced offer cannot be accepted by mistake.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be depositor or beneficiary, but not the proposer
+    /// - Escrow must be Disputed with no provisional ruling pending
+    /// - An offer must be open and match `split`
+    ///
+    /// EFFECTS:
+    /// - Clear dispute record and offer, then settle via the Mutual path
+    pub fn accept_settlement(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let mut escrow = Self::get_settleable(env, escrow_id, caller)?;
+
+        let offer =
+            EscrowStorage::get_offer(env, escrow_id).ok_or(EscrowError::NoSettlementOffer)?;
+        if offer.proposer == *caller {
+            return Err(EscrowError::NotAuthorized);
+        }
+        if offer.split != split {
+            return Err(EscrowError::OfferMismatch);
+        }
+
+        // EFFECTS: Clear dispute and offer, then settle
+        Self::close(env, &escrow);
+        EscrowStorage::clear_offer(env, escrow_id);
+        EscrowHandler::settle(env, &mut escrow, split.clone(), ResolutionPath::Mutual)?;
+        events::dispute_resolved(env, &escrow, caller, &split, &ResolutionPath::Mutual);
+
+        Ok(())
+    }
+
+    /// Withdraw a dispute, returning the escrow to Funded.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the party that opened the dispute
+    /// - Escrow must be Disputed with no provisional ruling pending
+    ///
+    /// EFFECTS:
+    /// - Update escrow status to Funded
+    /// - Clear dispute record, settlement offer, panel and approvals
+    /// - Reopen a contested deduction claim on its contested lines, so the depositor
+    ///   must still accept them or let them lapse; the escrow keeps holding only the
+    ///   contested amount
+    pub fn withdraw_dispute(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let mut escrow = Self::get_settleable(env, escrow_id, caller)?;
+
+        let dispute =
+            EscrowStorage::get_dispute(env, escrow_id).ok_or(EscrowError::InvalidState)?;
+        if dispute.opened_by != *caller {
+            return Err(EscrowError::NotAuthorized);
+        }
+
+        // EFFECTS: Return to Funded with a clean slate
+        escrow.status = EscrowStatus::Funded;
+        EscrowStorage::save(env, &escrow);
+        Self::close(env, &escrow);
+        EscrowStorage::clear_offer(env, escrow_id);
+        EscrowStorage::clear_panel(env, escrow_id);
         EscrowStorage::clear_approvals(env, escrow_id);
+        if let DisputeReason::ContestedDeductions(contested_lines) = &dispute.reason {
+            DeductionHandler::reopen_contested(env, escrow_id, contested_lines);
+        }
 
-        // INTERACTIONS: Token transfer would happen here
-        // transfer_to_token(&env, &escrow.token, &escrow_contract_id, &release_to, escrow.amount)?;
+        let amount = EscrowHandler::current_amount(env, &escrow);
+        events::dispute_withdrawn(env, &escrow, caller, dispute.category, amount);
 
         Ok(())
     }
 
+    /// Get the open settlement offer on an escrow's dispute, if any.
+    pub fn get_settlement_offer(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+    ) -> Result<Option<SettlementOffer>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_offer(env, escrow_id))
+    }
+
+    /// Load a Disputed escrow the primary parties can still settle between themselves:
+    /// `caller` must be depositor or beneficiary and no provisional ruling may be pending.
+    fn get_settleable(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+    ) -> Result<Escrow, EscrowError> {
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_primary_party(&escrow, caller)?;
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(EscrowError::InvalidState);
+        }
+        if EscrowStorage::get_ruling(env, escrow_id).is_some() {
+            return Err(EscrowError::RulingPending);
+        }
+
+        Ok(escrow)
+    }
+
+    /// Whether the party that did not open the dispute has submitted evidence.
+    fn counter_party_responded(escrow: &Escrow, dispute: &Dispute) -> bool {
+        let counter_party = if dispute.opened_by == escrow.depositor {
+            &escrow.beneficiary
+        } else {
+            &escrow.depositor
+        };
+        dispute
+            .evidence
+            .iter()
+            .any(|entry| entry.submitter == *counter_party)
+    }
+
     /// Get dispute information for an escrow.
-    /// Returns the dispute reason if escrow is disputed, None otherwise.
+    /// Returns the dispute record if escrow is disputed, None otherwise.
     pub fn get_dispute_info(
         env: &Env,
         escrow_id: &BytesN<32>,
-    ) -> Result<Option<String>, EscrowError> {
-        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+    ) -> Result<Option<Dispute>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
 
-        Ok(escrow.dispute_reason)
+        Ok(EscrowStorage::get_dispute(env, escrow_id))
     }
 
     /// Check if an escrow is currently disputed.
@@ -28,4 +28,82 @@ pub enum EscrowError {
     EmptyDisputeReason = 10,
     /// Invalid approval target (neither beneficiary nor depositor)
     InvalidApprovalTarget = 11,
+    /// Contract token balance is below the total held in escrows
+    BalanceMismatch = 12,
+    /// An escrow already exists for this agreement or ID
+    EscrowAlreadyExists = 13,
+    /// Split amounts are negative or do not add up to the escrowed amount
+    InvalidSplit = 14,
+    /// A deduction claim has already been filed for this escrow
+    ClaimAlreadyFiled = 15,
+    /// No deduction claim exists for this escrow
+    ClaimNotFound = 16,
+    /// Deduction claim lines are empty, non-positive or exceed the escrow
+    InvalidClaim = 17,
+    /// Deduction claim is not awaiting a response
+    ClaimNotOpen = 18,
+    /// Deduction claim response window has closed
+    ClaimWindowClosed = 19,
+    /// Deduction claim response window is still open
+    ClaimWindowOpen = 20,
+    /// Dispute already holds the maximum number of evidence entries
+    EvidenceLimitReached = 21,
+    /// Counter-party response window is still open
+    ResponseWindowOpen = 22,
+    /// Arbiter ruling window is still open
+    RulingWindowOpen = 23,
+    /// Contract has already been initialized
+    AlreadyInitialized = 24,
+    /// Contract has not been initialized
+    NotInitialized = 25,
+    /// Configuration values are invalid
+    InvalidConfig = 26,
+    /// Signer set is empty, has duplicates or zero weights, or its threshold is unreachable
+    InvalidSignerSet = 27,
+    /// Caller has no live approval for this split
+    ApprovalNotFound = 28,
+    /// No release-after time is set or it has not been reached
+    RefundNotDue = 29,
+    /// A provisional ruling has already been made on this dispute
+    RulingPending = 30,
+    /// No provisional ruling exists for this dispute
+    NoRuling = 31,
+    /// Appeal window of the provisional ruling has closed
+    AppealWindowClosed = 32,
+    /// Appeal window of the provisional ruling is still open
+    AppealWindowOpen = 33,
+    /// The provisional ruling has already been appealed
+    AlreadyAppealed = 34,
+    /// No appeal has been filed against the ruling
+    NoAppeal = 35,
+    /// Caller is not on the dispute's arbiter panel
+    NotPanelist = 36,
+    /// Panelist has already voted on this dispute
+    AlreadyVoted = 37,
+    /// Arbiter pool has too few eligible arbiters for the panel
+    PoolTooSmall = 38,
+    /// Arbiter is already in the pool
+    ArbiterInPool = 39,
+    /// Arbiter is not in the pool
+    ArbiterNotInPool = 40,
+    /// No settlement offer is open for this dispute
+    NoSettlementOffer = 41,
+    /// Accepted split does not match the open settlement offer
+    OfferMismatch = 42,
+    /// Arbiter is not in the registry
+    ArbiterNotRegistered = 43,
+    /// Arbiter is registered but not active
+    ArbiterInactive = 44,
+    /// Arbiter is already registered
+    ArbiterAlreadyRegistered = 45,
+    /// Bond is below the minimum or smaller than the amount to slash
+    InsufficientBond = 46,
+    /// Arbiter fee in the ruling does not match the registry fee
+    InvalidArbiterFee = 47,
+    /// Milestones are empty, non-positive or due out of order
+    InvalidMilestones = 48,
+    /// Yield vault reported a negative withdrawal
+    VaultShortfall = 49,
+    /// An adjustment request is already open
+    AdjustmentPending = 50,
 }
@@ -1,39 +1,56 @@
 //! Core escrow lifecycle logic: creation, funding, approvals, and release.
 //! Implements checks-effects-interactions pattern for reentrancy safety.
-use soroban_sdk::{Address, BytesN, Env, Vec};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, Bytes, BytesN, Env, String, Vec};
 
 use crate::access::AccessControl;
+use crate::custody::Custody;
 use crate::errors::EscrowError;
+use crate::events;
+use crate::funding::FundingHandler;
+use crate::milestone::MilestoneHandler;
+use crate::registry::ArbiterRegistry;
 use crate::storage::EscrowStorage;
-use crate::types::{Escrow, EscrowStatus, ReleaseApproval};
+use crate::types::{
+    ApprovalProgress, Escrow, EscrowStatus, ReleaseApproval, ReleaseSplit, ResolutionPath,
+    Settlement, Signer, SignerSet,
+};
+use crate::vault::YieldVault;
 
-/// Core escrow contract implementation.
-pub struct EscrowContract;
+/// Core escrow lifecycle handling.
+pub struct EscrowHandler;
 
-impl EscrowContract {
+impl EscrowHandler {
     /// Create a new escrow.
     ///
     /// CHECKS:
+    /// - Beneficiary (landlord) must authorize
     /// - Amount must be positive
     /// - All addresses must be distinct
+    /// - Arbiter must be registered and active
+    /// - Agreement must not already have an escrow
     ///
     /// EFFECTS:
+    /// - Derives the escrow ID from agreement_id, depositor and escrow counter
     /// - Creates new Escrow with Pending status
-    /// - Stores escrow in persistent storage
+    /// - Stores escrow in persistent storage and indexes it by agreement
     /// - Increments escrow counter
     ///
     /// INTERACTIONS:
-    /// - Token transfer from depositor (not yet implemented in this version)
-    ///   would happen after state update
+    /// - None; funds are pulled later by `fund_escrow`
     pub fn create(
         env: &Env,
+        agreement_id: String,
         depositor: Address,
         beneficiary: Address,
         arbiter: Address,
         amount: i128,
         token: Address,
     ) -> Result<BytesN<32>, EscrowError> {
-        // CHECKS: Validate inputs
+        // CHECKS: Beneficiary sets up the escrow terms
+        beneficiary.require_auth();
+
+        // Validate inputs
         if amount <= 0 {
             return Err(EscrowError::InsufficientFunds);
         }
@@ -43,48 +60,77 @@ impl EscrowContract {
             return Err(EscrowError::InvalidSigner);
         }
 
-        // Generate escrow ID - use a deterministic hash
-        // In production, use a proper hash function
-        let escrow_id = BytesN::<32>::from_array(env, &[0u8; 32]);
+        // Only bonded, active arbiters can be named
+        ArbiterRegistry::require_active(env, &arbiter)?;
+
+        // One escrow per agreement
+        if EscrowStorage::get_agreement_escrow(env, &agreement_id).is_some() {
+            return Err(EscrowError::EscrowAlreadyExists);
+        }
+
+        let escrow_id = Self::derive_id(env, &agreement_id, &depositor);
+        if EscrowStorage::get(env, &escrow_id).is_some() {
+            return Err(EscrowError::EscrowAlreadyExists);
+        }
 
         // EFFECTS: Create and save escrow
         let escrow = Escrow {
             id: escrow_id.clone(),
+            agreement_id: agreement_id.clone(),
             depositor: depositor.clone(),
             beneficiary: beneficiary.clone(),
             arbiter: arbiter.clone(),
             amount,
+            funded: 0,
             token,
             status: EscrowStatus::Pending,
             created_at: env.ledger().timestamp(),
-            dispute_reason: None,
         };
 
         EscrowStorage::save(env, &escrow);
+        EscrowStorage::set_agreement_escrow(env, &agreement_id, &escrow_id);
         EscrowStorage::increment_count(env);
+        events::escrow_created(env, &escrow);
 
         Ok(escrow_id)
     }
 
-    /// Fund an existing escrow by depositing funds.
-    /// Transitions status from Pending to Funded.
+    /// Derive a deterministic escrow ID:
+    /// sha256(xdr(agreement_id) || xdr(depositor) || escrow_count as big-endian u32).
+    pub fn derive_id(env: &Env, agreement_id: &String, depositor: &Address) -> BytesN<32> {
+        let mut preimage = Bytes::new(env);
+        preimage.append(&agreement_id.clone().to_xdr(env));
+        preimage.append(&depositor.clone().to_xdr(env));
+        preimage.extend_from_array(&EscrowStorage::get_count(env).to_be_bytes());
+
+        env.crypto().sha256(&preimage).to_bytes()
+    }
+
+    /// Fund an existing escrow by depositing funds: the next installment if the escrow
+    /// has a schedule, the whole outstanding amount otherwise.
+    /// Transitions status from Pending to Funded once the target amount is met.
     ///
     /// CHECKS:
+    /// - Caller must authorize
     /// - Escrow must exist
     /// - Escrow must be in Pending state
     /// - Caller must be depositor
     ///
     /// EFFECTS:
-    /// - Update escrow status to Funded
+    /// - Add the payment to the funded amount; update status to Funded on reaching the target
     ///
     /// INTERACTIONS:
-    /// - Token transfer would happen after state update (not yet in this version)
+    /// - Transfer the payment in `escrow.token` from depositor to the contract
+    /// - Once Funded, place the deposit in the escrow's yield vault, if it has one
     pub fn fund_escrow(
         env: &Env,
         escrow_id: &BytesN<32>,
         caller: &Address,
     ) -> Result<(), EscrowError> {
-        // CHECKS: Get and validate escrow
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        // Get and validate escrow
         let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
 
         // Verify caller is depositor
@@ -95,116 +141,444 @@ impl EscrowContract {
             return Err(EscrowError::InvalidState);
         }
 
-        // EFFECTS: Update status
-        escrow.status = EscrowStatus::Funded;
+        // EFFECTS: Record the payment and update status
+        let payment = FundingHandler::next_payment(env, &escrow);
+        escrow.funded += payment;
+        if escrow.funded == escrow.amount {
+            escrow.status = EscrowStatus::Funded;
+        }
         EscrowStorage::save(env, &escrow);
 
-        // INTERACTIONS: Token transfer would happen here
-        // transfer_from_token(&env, &escrow.token, &caller, &escrow_contract_id, escrow.amount)?;
+        // INTERACTIONS: Pull the payment into contract custody
+        Custody::deposit(env, &escrow.token, caller, payment)?;
+        if escrow.status == EscrowStatus::Funded {
+            YieldVault::place(env, &escrow)?;
+        }
+        events::escrow_funded(env, &escrow, payment);
+
+        Ok(())
+    }
+
+    /// Replace the default signer set of a Pending escrow.
+    /// The depositor must co-sign, since the set decides who can release its deposit.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the beneficiary
+    /// - Depositor must authorize
+    /// - Escrow must be in Pending state
+    /// - Signers must be non-empty, unique and have positive weights
+    /// - Threshold must be positive and reachable by the total weight
+    ///
+    /// EFFECTS:
+    /// - Store the signer set; it governs every later `approve_release`
+    pub fn set_signers(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        signers: Vec<Signer>,
+        threshold: u32,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_beneficiary(&escrow, caller)?;
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Pending {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let mut total_weight: u32 = 0;
+        for (position, signer) in signers.iter().enumerate() {
+            if signer.weight == 0 {
+                return Err(EscrowError::InvalidSignerSet);
+            }
+            let first = signers
+                .iter()
+                .position(|other| other.address == signer.address);
+            if first != Some(position) {
+                return Err(EscrowError::InvalidSignerSet);
+            }
+            total_weight = total_weight
+                .checked_add(signer.weight)
+                .ok_or(EscrowError::InvalidSignerSet)?;
+        }
+        if threshold == 0 || threshold > total_weight {
+            return Err(EscrowError::InvalidSignerSet);
+        }
+
+        // EFFECTS: Store the signer set
+        EscrowStorage::save_signers(env, escrow_id, &SignerSet { signers, threshold });
+
+        Ok(())
+    }
+
+    /// Set how long approvals on a Pending escrow stay live, in seconds (0 = never expire).
+    /// The depositor must co-sign, since the TTL decides which approvals release its deposit.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the beneficiary
+    /// - Depositor must authorize
+    /// - Escrow must be in Pending state
+    pub fn set_approval_ttl(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        ttl: u64,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_beneficiary(&escrow, caller)?;
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Pending {
+            return Err(EscrowError::InvalidState);
+        }
+
+        // EFFECTS: Store the TTL
+        EscrowStorage::set_approval_ttl(env, escrow_id, ttl);
 
         Ok(())
     }
 
-    /// Approve release of funds to a target address.
-    /// Implements 2-of-3 multi-sig: executes transfer when ≥2 unique signers approve same target.
+    /// Set the time after which a Pending escrow's deposit can be refunded if nobody
+    /// contests it, typically the agreement end date plus a claim window.
+    /// The depositor must co-sign, since the time decides when it gets its deposit back.
     ///
     /// CHECKS:
-    /// - Escrow must exist and be Funded (or Disputed if arbiter)
-    /// - Caller must be a valid party
-    /// - Release target must be beneficiary or depositor
-    /// - Caller must not have already approved this same target
+    /// - Caller must authorize and be the beneficiary
+    /// - Depositor must authorize
+    /// - Escrow must be in Pending state
+    /// - Release-after time must be in the future
+    pub fn set_release_after(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        release_after: u64,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_beneficiary(&escrow, caller)?;
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Pending {
+            return Err(EscrowError::InvalidState);
+        }
+
+        if release_after <= env.ledger().timestamp() {
+            return Err(EscrowError::InvalidConfig);
+        }
+
+        // EFFECTS: Store the release-after time
+        EscrowStorage::set_release_after(env, escrow_id, release_after);
+
+        Ok(())
+    }
+
+    /// Refund the whole deposit once the release-after time has passed.
+    /// Anyone may call this; a dispute or deduction claim opened first blocks it.
+    /// A Pending escrow that was only partly funded refunds the installments paid,
+    /// and can be refunded early once an installment is overdue.
+    ///
+    /// CHECKS:
+    /// - Escrow must be Funded (not Disputed), or Pending with some funds paid in
+    /// - Release-after time must be set and reached, unless the escrow is partly
+    ///   funded and an installment is past its due date unpaid
+    /// - No deduction claim may have been filed
+    ///
+    /// EFFECTS:
+    /// - Settle the escrow as Refunded via the Timeout path
+    ///
+    /// INTERACTIONS:
+    /// - Transfer the amount up for release (the current milestone's, on a
+    ///   milestone escrow; exactly what was paid in, on a Pending escrow) back
+    ///   to the depositor
+    pub fn trigger_refund(env: &Env, escrow_id: &BytesN<32>) -> Result<(), EscrowError> {
+        let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+
+        let partly_funded = escrow.status == EscrowStatus::Pending && escrow.funded > 0;
+        if escrow.status != EscrowStatus::Funded && !partly_funded {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let release_after = EscrowStorage::get_release_after(env, escrow_id);
+        let overdue = partly_funded && FundingHandler::installment_overdue(env, &escrow);
+        if !overdue && (release_after == 0 || env.ledger().timestamp() < release_after) {
+            return Err(EscrowError::RefundNotDue);
+        }
+
+        if EscrowStorage::get_claim(env, escrow_id).is_some() {
+            return Err(EscrowError::ClaimAlreadyFiled);
+        }
+
+        let refund = if partly_funded {
+            escrow.funded
+        } else {
+            Self::current_amount(env, &escrow)
+        };
+        let split = ReleaseSplit {
+            beneficiary_amount: 0,
+            depositor_amount: refund,
+            arbiter_fee: 0,
+        };
+        Self::settle(env, &mut escrow, split, ResolutionPath::Timeout)
+    }
+
+    /// Approve release of funds according to a split.
+    /// Executes the transfer once the weight of unique signers approving the exact same
+    /// split reaches the escrow's threshold (2-of-3 by default).
+    ///
+    /// CHECKS:
+    /// - Caller must authorize
+    /// - Escrow must exist and be Funded
+    /// - Caller must be in the escrow's signer set
+    /// - Split must be non-negative and add up to the escrowed amount
+    /// - Caller must not have already approved this same split
     ///
     /// EFFECTS:
     /// - Add approval to storage
-    /// - Count approvals; if ≥2 unique parties approve same target, update escrow status
-    /// - Clear approvals after execution
+    /// - Sum approving weight; if it reaches the threshold, settle the escrow
     ///
     /// INTERACTIONS:
-    /// - Token transfer after all state updates
+    /// - Token transfer after all state updates (see `settle`)
     pub fn approve_release(
         env: &Env,
         escrow_id: &BytesN<32>,
         caller: &Address,
-        release_to: Address,
+        split: ReleaseSplit,
     ) -> Result<(), EscrowError> {
-        // CHECKS: Get and validate escrow
-        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        // CHECKS: Caller must authorize
+        caller.require_auth();
 
-        // Verify caller is a valid party
-        AccessControl::is_party(&escrow, caller)?;
+        // Get and validate escrow
+        let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+
+        // Verify caller is a signer
+        let signer_set = EscrowStorage::get_signers(env, &escrow);
+        AccessControl::is_signer(&signer_set, caller)?;
 
         // Verify escrow is in Funded state
         if escrow.status != EscrowStatus::Funded {
             return Err(EscrowError::InvalidState);
         }
 
-        // Verify release target is valid (must be beneficiary or depositor)
-        if release_to != escrow.beneficiary && release_to != escrow.depositor {
-            return Err(EscrowError::InvalidApprovalTarget);
-        }
-
-        // Get existing approvals
-        let approvals = EscrowStorage::get_approvals(env, escrow_id);
+        // Verify split accounts for exactly the escrowed amount
+        Self::validate_split(env, &escrow, &split)?;
 
-        // Check for duplicate approval from same signer to same target
+        // Check for a live duplicate approval from same signer for the same split
+        let mut approvals = Self::live_approvals(env, escrow_id);
         for approval in &approvals {
-            if approval.signer == *caller && approval.release_to == release_to {
+            if approval.signer == *caller && approval.split == split {
                 return Err(EscrowError::AlreadySigned);
             }
         }
 
-        // EFFECTS: Add the new approval
-        let new_approval = ReleaseApproval {
+        // EFFECTS: Add the new approval, dropping any that have expired
+        approvals.push_back(ReleaseApproval {
             signer: caller.clone(),
-            release_to: release_to.clone(),
+            split: split.clone(),
             timestamp: env.ledger().timestamp(),
-        };
-        EscrowStorage::add_approval(env, escrow_id, new_approval);
+        });
+        EscrowStorage::save_approvals(env, escrow_id, &approvals);
+
+        // Release once the approving weight reaches the threshold
+        let weight = Self::approved_weight(&signer_set, &approvals, &split);
+        events::approval_added(env, &escrow, caller, &split, weight, signer_set.threshold);
+        if weight >= signer_set.threshold {
+            Self::settle(env, &mut escrow, split, ResolutionPath::Multisig)?;
+        }
 
-        // Count unique signers approving this release target
-        let mut unique_signers: Vec<Address> = Vec::new(env);
-        for approval in &approvals {
-            if approval.release_to == release_to {
-                let mut found = false;
-                for signer in &unique_signers {
-                    if signer == approval.signer {
-                        found = true;
-                        break;
-                    }
-                }
-                if !found {
-                    unique_signers.push_back(approval.signer.clone());
-                }
-            }
+        Ok(())
+    }
+
+    /// Withdraw the caller's live approval of `split`.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize
+    /// - Escrow must exist and be Funded
+    /// - Caller must have a live approval for exactly this split
+    ///
+    /// EFFECTS:
+    /// - Remove the approval, dropping any that have expired
+    pub fn revoke_approval(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        if escrow.status != EscrowStatus::Funded {
+            return Err(EscrowError::InvalidState);
         }
 
-        // Add current caller to unique signers count (since we just added their approval)
-        let mut caller_already_counted = false;
-        for signer in &unique_signers {
-            if signer == *caller {
-                caller_already_counted = true;
-                break;
+        let mut approvals = Self::live_approvals(env, escrow_id);
+        let position = approvals
+            .iter()
+            .position(|approval| approval.signer == *caller && approval.split == split)
+            .ok_or(EscrowError::ApprovalNotFound)?;
+
+        // EFFECTS: Remove the approval
+        approvals.remove(position as u32);
+        EscrowStorage::save_approvals(env, escrow_id, &approvals);
+        events::approval_revoked(env, &escrow, caller, &split);
+
+        Ok(())
+    }
+
+    /// Approvals recorded within the escrow's approval TTL.
+    /// An approval made at `t` stops counting at `t + ttl`; a TTL of 0 never expires.
+    pub fn live_approvals(env: &Env, escrow_id: &BytesN<32>) -> Vec<ReleaseApproval> {
+        let approvals = EscrowStorage::get_approvals(env, escrow_id);
+        let ttl = EscrowStorage::get_approval_ttl(env, escrow_id);
+        if ttl == 0 {
+            return approvals;
+        }
+
+        let now = env.ledger().timestamp();
+        let mut live = Vec::new(env);
+        for approval in approvals {
+            if approval.timestamp.saturating_add(ttl) > now {
+                live.push_back(approval);
             }
         }
-        if !caller_already_counted {
-            unique_signers.push_back(caller.clone());
+        live
+    }
+
+    /// Sum the weights of the unique signers that approved exactly `split`.
+    /// Approvals from addresses outside the signer set count for nothing.
+    pub fn approved_weight(
+        signer_set: &SignerSet,
+        approvals: &Vec<ReleaseApproval>,
+        split: &ReleaseSplit,
+    ) -> u32 {
+        signer_set
+            .signers
+            .iter()
+            .filter(|signer| {
+                approvals
+                    .iter()
+                    .any(|approval| approval.signer == signer.address && approval.split == *split)
+            })
+            .map(|signer| signer.weight)
+            .sum()
+    }
+
+    /// Amount up for release: the current milestone's amount on a milestone escrow,
+    /// the whole funded amount otherwise.
+    pub fn current_amount(env: &Env, escrow: &Escrow) -> i128 {
+        MilestoneHandler::current(env, escrow)
+            .map(|(_, milestone)| milestone.amount)
+            .unwrap_or(escrow.funded)
+    }
+
+    /// Verify a split is non-negative and adds up to the amount up for release.
+    pub fn validate_split(
+        env: &Env,
+        escrow: &Escrow,
+        split: &ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        if split.beneficiary_amount < 0 || split.depositor_amount < 0 || split.arbiter_fee < 0 {
+            return Err(EscrowError::InvalidSplit);
         }
 
-        // If 2 or more unique signers approve, execute release
-        if unique_signers.len() >= 2 {
-            let mut escrow_to_update =
-                EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        let total = split
+            .beneficiary_amount
+            .checked_add(split.depositor_amount)
+            .and_then(|sum| sum.checked_add(split.arbiter_fee))
+            .ok_or(EscrowError::InvalidSplit)?;
+        if total != Self::current_amount(env, escrow) {
+            return Err(EscrowError::InvalidSplit);
+        }
 
-            // Determine final status based on release target
-            escrow_to_update.status = EscrowStatus::Released;
-            EscrowStorage::save(env, &escrow_to_update);
+        Ok(())
+    }
 
-            // Clear approvals after execution
-            EscrowStorage::clear_approvals(env, escrow_id);
+    /// Settle an escrow by paying out the amount up for release according to `split`.
+    /// Status becomes Refunded when nothing went to the beneficiary, Released otherwise.
+    /// On a milestone escrow this settles the current milestone only; the escrow goes
+    /// back to Funded until its last milestone is settled.
+    ///
+    /// INTERACTIONS:
+    /// - Withdraw vaulted funds and share out their yield (see `YieldVault::recall`);
+    ///   if the vault returned less than was placed, scale the split down pro rata
+    ///
+    /// EFFECTS:
+    /// - Record the settlement (split, path) for the escrow or milestone
+    /// - Clear approvals
+    ///
+    /// INTERACTIONS:
+    /// - Transfer each non-zero share from the contract to beneficiary, depositor and arbiter
+    /// - Emit `EscrowSettled`
+    pub fn settle(
+        env: &Env,
+        escrow: &mut Escrow,
+        mut split: ReleaseSplit,
+        path: ResolutionPath,
+    ) -> Result<(), EscrowError> {
+        // INTERACTIONS: Bring back any vaulted funds first, so the recorded
+        // split is what is actually paid out
+        let total = Self::current_amount(env, escrow);
+        let loss = YieldVault::recall(env, escrow)?;
+        if loss > 0 {
+            let mut parts = [
+                split.beneficiary_amount,
+                split.arbiter_fee,
+                split.depositor_amount,
+            ];
+            YieldVault::share_loss(&mut parts, total, loss);
+            [
+                split.beneficiary_amount,
+                split.arbiter_fee,
+                split.depositor_amount,
+            ] = parts;
+        }
 
-            // INTERACTIONS: Token transfer would happen here
-            // transfer_to_token(&env, &escrow.token, &escrow_contract_id, &release_to, escrow.amount)?;
+        // EFFECTS: Record the outcome before paying out
+        let settlement = Settlement {
+            split,
+            path,
+            settled_at: env.ledger().timestamp(),
+        };
+        let outcome = match MilestoneHandler::current(env, escrow) {
+            Some((index, milestone)) => {
+                MilestoneHandler::record_settlement(env, escrow, index, &milestone, &settlement)
+            }
+            None => Some(settlement.clone()),
+        };
+        match outcome {
+            Some(outcome) => {
+                escrow.status = if outcome.split.beneficiary_amount == 0 {
+                    EscrowStatus::Refunded
+                } else {
+                    EscrowStatus::Released
+                };
+                EscrowStorage::save_settlement(env, &escrow.id, &outcome);
+            }
+            None => escrow.status = EscrowStatus::Funded,
         }
+        EscrowStorage::save(env, escrow);
+        EscrowStorage::clear_approvals(env, &escrow.id);
+
+        // INTERACTIONS: Pay out each share
+        let payouts = [
+            (&escrow.beneficiary, settlement.split.beneficiary_amount),
+            (&escrow.depositor, settlement.split.depositor_amount),
+            (&escrow.arbiter, settlement.split.arbiter_fee),
+        ];
+        for (recipient, amount) in payouts {
+            if amount > 0 {
+                Custody::pay_out(env, &escrow.token, recipient, amount)?;
+            }
+        }
+        events::escrow_settled(env, escrow, &settlement);
 
         Ok(())
     }
@@ -215,31 +589,59 @@ impl EscrowContract {
         EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)
     }
 
-    /// Get approval count for a specific release target.
-    /// Returns number of unique signers approving release to a specific address.
-    pub fn get_approval_count(
+    /// Get the escrow created for a rent agreement.
+    pub fn get_escrow_by_agreement(
+        env: &Env,
+        agreement_id: &String,
+    ) -> Result<Escrow, EscrowError> {
+        let escrow_id = EscrowStorage::get_agreement_escrow(env, agreement_id)
+            .ok_or(EscrowError::EscrowNotFound)?;
+        Self::get_escrow(env, &escrow_id)
+    }
+
+    /// Get the final payout record of a settled escrow.
+    pub fn get_settlement(
         env: &Env,
         escrow_id: &BytesN<32>,
-        release_to: &Address,
-    ) -> Result<u32, EscrowError> {
-        let approvals = EscrowStorage::get_approvals(env, escrow_id);
+    ) -> Result<Option<Settlement>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_settlement(env, escrow_id))
+    }
 
-        let mut unique_signers: Vec<Address> = Vec::new(env);
-        for approval in &approvals {
-            if approval.release_to == *release_to {
-                let mut found = false;
-                for signer in &unique_signers {
-                    if signer == approval.signer {
-                        found = true;
-                        break;
-                    }
-                }
-                if !found {
-                    unique_signers.push_back(approval.signer.clone());
-                }
-            }
-        }
+    /// Get the signer set governing an escrow's release.
+    pub fn get_signers(env: &Env, escrow_id: &BytesN<32>) -> Result<SignerSet, EscrowError> {
+        let escrow = Self::get_escrow(env, escrow_id)?;
+        Ok(EscrowStorage::get_signers(env, &escrow))
+    }
+
+    /// Get the time after which an uncontested escrow can be refunded (0 if unset).
+    pub fn get_release_after(env: &Env, escrow_id: &BytesN<32>) -> Result<u64, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_release_after(env, escrow_id))
+    }
+
+    /// Get the approvals on an escrow that are still live.
+    pub fn get_approvals(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+    ) -> Result<Vec<ReleaseApproval>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(Self::live_approvals(env, escrow_id))
+    }
 
-        Ok(unique_signers.len())
+    /// Get approval progress for a specific split.
+    /// Reports the weight of unique signers approving exactly this split against the threshold.
+    pub fn get_approval_count(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        split: &ReleaseSplit,
+    ) -> Result<ApprovalProgress, EscrowError> {
+        let signer_set = Self::get_signers(env, escrow_id)?;
+        let approvals = Self::live_approvals(env, escrow_id);
+
+        Ok(ApprovalProgress {
+            weight: Self::approved_weight(&signer_set, &approvals, split),
+            threshold: signer_set.threshold,
+        })
     }
 }
@@ -0,0 +1,298 @@
+//! Contract events for the Escrow contract.
+use soroban_sdk::{contractevent, Address, BytesN, Env, String};
+
+use crate::types::{
+    DisputeCategory, Escrow, EscrowStatus, ReleaseSplit, ResolutionPath, Settlement,
+};
+
+/// Emitted when an escrow is created.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowCreated {
+    pub escrow_id: BytesN<32>,
+    pub agreement_id: String,
+    pub depositor: Address,
+    pub beneficiary: Address,
+    pub arbiter: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub status: EscrowStatus,
+}
+
+/// Emitted for each payment the depositor makes towards an escrow.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowFunded {
+    pub escrow_id: BytesN<32>,
+    pub depositor: Address,
+    pub payment: i128,
+    pub funded: i128,
+    pub amount: i128,
+    pub status: EscrowStatus,
+}
+
+/// Emitted when a signer approves a release split.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalAdded {
+    pub escrow_id: BytesN<32>,
+    pub signer: Address,
+    pub beneficiary_amount: i128,
+    pub depositor_amount: i128,
+    pub arbiter_fee: i128,
+    pub approved_weight: u32,
+    pub threshold: u32,
+    pub status: EscrowStatus,
+}
+
+/// Emitted when a signer withdraws its approval of a release split.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalRevoked {
+    pub escrow_id: BytesN<32>,
+    pub signer: Address,
+    pub beneficiary_amount: i128,
+    pub depositor_amount: i128,
+    pub arbiter_fee: i128,
+    pub status: EscrowStatus,
+}
+
+/// Emitted when a dispute freezes an escrow.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeOpened {
+    pub escrow_id: BytesN<32>,
+    pub opened_by: Address,
+    pub category: DisputeCategory,
+    pub amount: i128,
+    pub response_deadline: u64,
+    pub status: EscrowStatus,
+}
+
+/// Emitted when a party adds evidence to a dispute.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EvidenceAdded {
+    pub escrow_id: BytesN<32>,
+    pub submitter: Address,
+    pub content_hash: BytesN<32>,
+    pub evidence_count: u32,
+    pub status: EscrowStatus,
+}
+
+/// Emitted when a dispute is decided by a ruling, an appeal or a mutual settlement.
+/// A provisional ruling still open to appeal leaves the status at Disputed.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeResolved {
+    pub escrow_id: BytesN<32>,
+    pub resolved_by: Address,
+    pub beneficiary_amount: i128,
+    pub depositor_amount: i128,
+    pub arbiter_fee: i128,
+    pub path: ResolutionPath,
+    pub status: EscrowStatus,
+}
+
+/// Emitted when the party that opened a dispute withdraws it, returning the escrow
+/// to Funded.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeWithdrawn {
+    pub escrow_id: BytesN<32>,
+    pub withdrawn_by: Address,
+    pub category: DisputeCategory,
+    pub amount: i128,
+    pub status: EscrowStatus,
+}
+
+/// Emitted when an escrow pays out and reaches a final status, or when one milestone
+/// of a milestone escrow pays out (status back to Funded until the last one).
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowSettled {
+    pub escrow_id: BytesN<32>,
+    pub beneficiary: Address,
+    pub beneficiary_amount: i128,
+    pub depositor: Address,
+    pub depositor_amount: i128,
+    pub arbiter: Address,
+    pub arbiter_fee: i128,
+    pub path: ResolutionPath,
+    pub status: EscrowStatus,
+}
+
+/// Emitted when the uncontested part of a deduction claim pays out ahead of the
+/// dispute over the rest; `held` is the amount that stays in escrow.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartialRelease {
+    pub escrow_id: BytesN<32>,
+    pub beneficiary: Address,
+    pub beneficiary_amount: i128,
+    pub depositor: Address,
+    pub depositor_amount: i128,
+    pub held: i128,
+    pub path: ResolutionPath,
+    pub status: EscrowStatus,
+}
+
+pub(crate) fn escrow_created(env: &Env, escrow: &Escrow) {
+    EscrowCreated {
+        escrow_id: escrow.id.clone(),
+        agreement_id: escrow.agreement_id.clone(),
+        depositor: escrow.depositor.clone(),
+        beneficiary: escrow.beneficiary.clone(),
+        arbiter: escrow.arbiter.clone(),
+        token: escrow.token.clone(),
+        amount: escrow.amount,
+        status: escrow.status.clone(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn escrow_funded(env: &Env, escrow: &Escrow, payment: i128) {
+    EscrowFunded {
+        escrow_id: escrow.id.clone(),
+        depositor: escrow.depositor.clone(),
+        payment,
+        funded: escrow.funded,
+        amount: escrow.amount,
+        status: escrow.status.clone(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn approval_added(
+    env: &Env,
+    escrow: &Escrow,
+    signer: &Address,
+    split: &ReleaseSplit,
+    approved_weight: u32,
+    threshold: u32,
+) {
+    ApprovalAdded {
+        escrow_id: escrow.id.clone(),
+        signer: signer.clone(),
+        beneficiary_amount: split.beneficiary_amount,
+        depositor_amount: split.depositor_amount,
+        arbiter_fee: split.arbiter_fee,
+        approved_weight,
+        threshold,
+        status: escrow.status.clone(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn approval_revoked(env: &Env, escrow: &Escrow, signer: &Address, split: &ReleaseSplit) {
+    ApprovalRevoked {
+        escrow_id: escrow.id.clone(),
+        signer: signer.clone(),
+        beneficiary_amount: split.beneficiary_amount,
+        depositor_amount: split.depositor_amount,
+        arbiter_fee: split.arbiter_fee,
+        status: escrow.status.clone(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn dispute_opened(
+    env: &Env,
+    escrow: &Escrow,
+    opened_by: &Address,
+    category: DisputeCategory,
+    amount: i128,
+    response_deadline: u64,
+) {
+    DisputeOpened {
+        escrow_id: escrow.id.clone(),
+        opened_by: opened_by.clone(),
+        category,
+        amount,
+        response_deadline,
+        status: escrow.status.clone(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn evidence_added(
+    env: &Env,
+    escrow: &Escrow,
+    submitter: &Address,
+    content_hash: &BytesN<32>,
+    evidence_count: u32,
+) {
+    EvidenceAdded {
+        escrow_id: escrow.id.clone(),
+        submitter: submitter.clone(),
+        content_hash: content_hash.clone(),
+        evidence_count,
+        status: escrow.status.clone(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn dispute_resolved(
+    env: &Env,
+    escrow: &Escrow,
+    resolved_by: &Address,
+    split: &ReleaseSplit,
+    path: &ResolutionPath,
+) {
+    DisputeResolved {
+        escrow_id: escrow.id.clone(),
+        resolved_by: resolved_by.clone(),
+        beneficiary_amount: split.beneficiary_amount,
+        depositor_amount: split.depositor_amount,
+        arbiter_fee: split.arbiter_fee,
+        path: path.clone(),
+        status: escrow.status.clone(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn dispute_withdrawn(
+    env: &Env,
+    escrow: &Escrow,
+    withdrawn_by: &Address,
+    category: DisputeCategory,
+    amount: i128,
+) {
+    DisputeWithdrawn {
+        escrow_id: escrow.id.clone(),
+        withdrawn_by: withdrawn_by.clone(),
+        category,
+        amount,
+        status: escrow.status.clone(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn escrow_settled(env: &Env, escrow: &Escrow, settlement: &Settlement) {
+    EscrowSettled {
+        escrow_id: escrow.id.clone(),
+        beneficiary: escrow.beneficiary.clone(),
+        beneficiary_amount: settlement.split.beneficiary_amount,
+        depositor: escrow.depositor.clone(),
+        depositor_amount: settlement.split.depositor_amount,
+        arbiter: escrow.arbiter.clone(),
+        arbiter_fee: settlement.split.arbiter_fee,
+        path: settlement.path.clone(),
+        status: escrow.status.clone(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn partial_release(env: &Env, escrow: &Escrow, release: &Settlement) {
+    PartialRelease {
+        escrow_id: escrow.id.clone(),
+        beneficiary: escrow.beneficiary.clone(),
+        beneficiary_amount: release.split.beneficiary_amount,
+        depositor: escrow.depositor.clone(),
+        depositor_amount: release.split.depositor_amount,
+        held: escrow.funded,
+        path: release.path.clone(),
+        status: escrow.status.clone(),
+    }
+    .publish(env);
+}
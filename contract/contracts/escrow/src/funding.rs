@@ -0,0 +1,248 @@
+//! Installment funding and amount adjustments for the Escrow contract.
+//! A Pending escrow can be funded in scheduled installments and becomes Funded once
+//! its target is met. Once an installment is past its due date unpaid, the escrow
+//! can be refunded right away. Once Funded, the beneficiary can ask to raise or lower the
+//! amount; the change only takes effect when the depositor approves it.
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use crate::access::AccessControl;
+use crate::custody::Custody;
+use crate::errors::EscrowError;
+use crate::storage::EscrowStorage;
+use crate::types::{AdjustmentRequest, Escrow, EscrowStatus, Installment};
+
+/// Installment schedules and amount adjustments.
+pub struct FundingHandler;
+
+impl FundingHandler {
+    /// Let the depositor fund a Pending escrow in scheduled installments.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the beneficiary
+    /// - Escrow must be in Pending state with nothing funded yet and not released
+    ///   in milestones
+    /// - At least one installment, every amount positive, due dates in order
+    /// - Installments must add up to the escrow amount
+    ///
+    /// EFFECTS:
+    /// - Store the schedule; each `fund_escrow` then pays the next installment, and
+    ///   a missed due date lets `trigger_refund` return what was paid in
+    pub fn set_installments(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        installments: Vec<Installment>,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_beneficiary(&escrow, caller)?;
+
+        // Milestone escrows pay out per milestone, which a partial funding cannot cover
+        if escrow.status != EscrowStatus::Pending
+            || escrow.funded > 0
+            || EscrowStorage::get_milestones(env, escrow_id).is_some()
+        {
+            return Err(EscrowError::InvalidState);
+        }
+
+        if installments.is_empty() {
+            return Err(EscrowError::InvalidConfig);
+        }
+
+        let mut total: i128 = 0;
+        let mut last_due = 0;
+        for installment in &installments {
+            if installment.amount <= 0 || installment.due_date < last_due {
+                return Err(EscrowError::InvalidConfig);
+            }
+            total = total
+                .checked_add(installment.amount)
+                .ok_or(EscrowError::InvalidConfig)?;
+            last_due = installment.due_date;
+        }
+        if total != escrow.amount {
+            return Err(EscrowError::InvalidConfig);
+        }
+
+        // EFFECTS: Store the schedule
+        EscrowStorage::save_installments(env, escrow_id, &installments);
+
+        Ok(())
+    }
+
+    /// Amount the next `fund_escrow` pays: the rest of the current installment, or the
+    /// whole outstanding amount when the escrow has no schedule.
+    pub fn next_payment(env: &Env, escrow: &Escrow) -> i128 {
+        let outstanding = escrow.amount - escrow.funded;
+        let Some(installments) = EscrowStorage::get_installments(env, &escrow.id) else {
+            return outstanding;
+        };
+
+        let mut scheduled: i128 = 0;
+        for installment in installments {
+            scheduled += installment.amount;
+            if scheduled > escrow.funded {
+                return (scheduled - escrow.funded).min(outstanding);
+            }
+        }
+        outstanding
+    }
+
+    /// Whether an installment of the escrow's schedule is past its due date without
+    /// having been paid in full.
+    pub fn installment_overdue(env: &Env, escrow: &Escrow) -> bool {
+        let Some(installments) = EscrowStorage::get_installments(env, &escrow.id) else {
+            return false;
+        };
+
+        let now = env.ledger().timestamp();
+        let mut scheduled: i128 = 0;
+        for installment in installments {
+            if installment.due_date >= now {
+                return false;
+            }
+            scheduled += installment.amount;
+            if scheduled > escrow.funded {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Ask to change a Funded escrow's amount, e.g. after a rent increase.
+    /// A new request cannot be made while another is open.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the beneficiary
+    /// - Escrow must be Funded, not released in milestones and not in a yield vault
+    /// - New amount must be positive and differ from the current one
+    ///
+    /// EFFECTS:
+    /// - Store the request for the depositor to approve or reject
+    pub fn request_adjustment(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        new_amount: i128,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_beneficiary(&escrow, caller)?;
+
+        if escrow.status != EscrowStatus::Funded
+            || EscrowStorage::get_milestones(env, escrow_id).is_some()
+            || EscrowStorage::get_yield_policy(env, escrow_id).is_some()
+        {
+            return Err(EscrowError::InvalidState);
+        }
+
+        if new_amount <= 0 {
+            return Err(EscrowError::InsufficientFunds);
+        }
+        if new_amount == escrow.amount {
+            return Err(EscrowError::InvalidConfig);
+        }
+
+        if EscrowStorage::get_adjustment(env, escrow_id).is_some() {
+            return Err(EscrowError::AdjustmentPending);
+        }
+
+        // EFFECTS: Store the request
+        let request = AdjustmentRequest {
+            new_amount,
+            requested_at: env.ledger().timestamp(),
+        };
+        EscrowStorage::save_adjustment(env, escrow_id, &request);
+
+        Ok(())
+    }
+
+    /// Approve the open adjustment request (depositor only). A top-up is paid in by
+    /// the depositor; a reduction is refunded to it.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the depositor
+    /// - Escrow must be Funded with an open request
+    ///
+    /// EFFECTS:
+    /// - Set amount and funded amount to the requested amount
+    /// - Clear the request and any release approvals for the old amount
+    ///
+    /// INTERACTIONS:
+    /// - Pull the top-up from the depositor, or pay the difference back to it
+    pub fn approve_adjustment(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_depositor(&escrow, caller)?;
+
+        if escrow.status != EscrowStatus::Funded {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let request =
+            EscrowStorage::get_adjustment(env, escrow_id).ok_or(EscrowError::InvalidState)?;
+
+        // EFFECTS: Apply the new amount
+        let difference = request.new_amount - escrow.funded;
+        escrow.amount = request.new_amount;
+        escrow.funded = request.new_amount;
+        EscrowStorage::save(env, &escrow);
+        EscrowStorage::clear_adjustment(env, escrow_id);
+        EscrowStorage::clear_approvals(env, escrow_id);
+
+        // INTERACTIONS: Settle the difference with the depositor
+        if difference > 0 {
+            Custody::deposit(env, &escrow.token, caller, difference)?;
+        } else if difference < 0 {
+            Custody::pay_out(env, &escrow.token, caller, -difference)?;
+        }
+
+        Ok(())
+    }
+
+    /// Drop the open adjustment request. The depositor rejects it or the beneficiary
+    /// withdraws it.
+    pub fn reject_adjustment(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+    ) -> Result<(), EscrowError> {
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_primary_party(&escrow, caller)?;
+
+        EscrowStorage::get_adjustment(env, escrow_id).ok_or(EscrowError::InvalidState)?;
+        EscrowStorage::clear_adjustment(env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Get the installment schedule of an escrow; empty when it is funded in one go.
+    pub fn get_installments(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+    ) -> Result<Vec<Installment>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_installments(env, escrow_id).unwrap_or(Vec::new(env)))
+    }
+
+    /// Get the open adjustment request on an escrow, if any.
+    pub fn get_adjustment(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+    ) -> Result<Option<AdjustmentRequest>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_adjustment(env, escrow_id))
+    }
+}
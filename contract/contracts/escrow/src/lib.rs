@@ -6,20 +6,738 @@
 //! Manages security deposit escrows with 2-of-3 multi-sig release mechanism.
 //! Supports dispute resolution with arbiter involvement.
 
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+
 pub mod access;
+pub mod appeal;
+pub mod custody;
+pub mod deductions;
 pub mod dispute;
 pub mod errors;
 pub mod escrow_impl;
+pub mod events;
+pub mod funding;
+pub mod milestone;
+pub mod panel;
+pub mod query;
+pub mod registry;
 pub mod storage;
 pub mod types;
+pub mod vault;
 
 #[cfg(test)]
 mod tests;
 
 // Re-export public APIs
 pub use access::AccessControl;
+pub use appeal::AppealHandler;
+pub use custody::Custody;
+pub use deductions::DeductionHandler;
 pub use dispute::DisputeHandler;
 pub use errors::EscrowError;
-pub use escrow_impl::EscrowContract;
+pub use escrow_impl::EscrowHandler;
+pub use events::{
+    ApprovalAdded, ApprovalRevoked, DisputeOpened, DisputeResolved, DisputeWithdrawn,
+    EscrowCreated, EscrowFunded, EscrowSettled, EvidenceAdded, PartialRelease,
+};
+pub use funding::FundingHandler;
+pub use milestone::MilestoneHandler;
+pub use panel::PanelHandler;
+pub use query::EscrowQuery;
+pub use registry::ArbiterRegistry;
 pub use storage::EscrowStorage;
-pub use types::{DataKey, Escrow, EscrowStatus, ReleaseApproval};
+pub use types::{
+    AdjustmentRequest, Appeal, ApprovalProgress, ArbiterProfile, ArbiterRegistryConfig,
+    ArbiterStats, ClaimStatus, ContractState, DataKey, DeductionCategory, DeductionClaim,
+    DeductionItem, Dispute, DisputeCategory, DisputeReason, Escrow, EscrowConfig, EscrowPage,
+    EscrowStatus, Evidence, Installment, Milestone, Panel, PanelVote, ProvisionalRuling,
+    ReleaseApproval, ReleaseSplit, ResolutionPath, Settlement, SettlementOffer, Signer, SignerSet,
+    VaultPosition, YieldPolicy,
+};
+pub use vault::{Vault, VaultClient, YieldVault};
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    /// Initialize the contract with an admin and configuration.
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - If the contract has already been initialized
+    /// * `InvalidConfig` - If the configuration parameters are invalid
+    pub fn initialize(env: Env, admin: Address, config: EscrowConfig) -> Result<(), EscrowError> {
+        if EscrowStorage::get_state(&env).is_some() {
+            return Err(EscrowError::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+
+        Self::validate_config(&config)?;
+
+        let state = ContractState {
+            admin,
+            config,
+            initialized: true,
+        };
+        EscrowStorage::save_state(&env, &state);
+
+        Ok(())
+    }
+
+    /// Get the contract admin and configuration, if initialized.
+    pub fn get_state(env: Env) -> Option<ContractState> {
+        EscrowStorage::get_state(&env)
+    }
+
+    /// Update the contract configuration. Requires authorization from the admin.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the contract has not been initialized
+    /// * `InvalidConfig` - If configuration values are invalid
+    pub fn update_config(env: Env, new_config: EscrowConfig) -> Result<(), EscrowError> {
+        let mut state = EscrowStorage::get_state(&env).ok_or(EscrowError::NotInitialized)?;
+
+        state.admin.require_auth();
+
+        Self::validate_config(&new_config)?;
+
+        state.config = new_config;
+        EscrowStorage::save_state(&env, &state);
+
+        Ok(())
+    }
+
+    /// Create a new escrow in Pending status for a rent agreement.
+    /// The arbiter must be registered and active in the arbiter registry.
+    /// Requires authorization from the beneficiary (landlord).
+    pub fn create(
+        env: Env,
+        agreement_id: String,
+        depositor: Address,
+        beneficiary: Address,
+        arbiter: Address,
+        amount: i128,
+        token: Address,
+    ) -> Result<BytesN<32>, EscrowError> {
+        EscrowHandler::create(
+            &env,
+            agreement_id,
+            depositor,
+            beneficiary,
+            arbiter,
+            amount,
+            token,
+        )
+    }
+
+    /// Create an escrow released in milestones, in order. The escrow amount is the sum
+    /// of the milestone amounts; each milestone is then approved, disputed and settled
+    /// on its own amount like a plain escrow.
+    pub fn create_milestone_escrow(
+        env: Env,
+        agreement_id: String,
+        depositor: Address,
+        beneficiary: Address,
+        arbiter: Address,
+        token: Address,
+        milestones: Vec<Milestone>,
+    ) -> Result<BytesN<32>, EscrowError> {
+        MilestoneHandler::create(
+            &env,
+            agreement_id,
+            depositor,
+            beneficiary,
+            arbiter,
+            token,
+            milestones,
+        )
+    }
+
+    /// Place the escrow's funds in a yield vault once funded (beneficiary only, while
+    /// Pending). On settlement the funds are withdrawn and the yield is shared out
+    /// between tenant, landlord and platform per the policy; a vault loss is shared
+    /// pro rata across the settlement. Requires authorization from the beneficiary
+    /// and the depositor.
+    pub fn set_yield_policy(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        policy: YieldPolicy,
+    ) -> Result<(), EscrowError> {
+        YieldVault::set_yield_policy(&env, &escrow_id, &caller, policy)
+    }
+
+    /// Let the depositor fund a Pending escrow in scheduled installments adding up to
+    /// its amount (beneficiary only, before any funds are paid in).
+    pub fn set_installments(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        installments: Vec<Installment>,
+    ) -> Result<(), EscrowError> {
+        FundingHandler::set_installments(&env, &escrow_id, &caller, installments)
+    }
+
+    /// Fund a Pending escrow. Requires authorization from the depositor.
+    /// Pays the next installment if the escrow has a schedule, the whole amount
+    /// otherwise; the escrow becomes Funded once its amount is met.
+    pub fn fund_escrow(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        EscrowHandler::fund_escrow(&env, &escrow_id, &caller)
+    }
+
+    /// Ask to raise or lower a Funded escrow's amount (beneficiary only).
+    /// Takes effect only once the depositor approves it.
+    pub fn request_adjustment(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        new_amount: i128,
+    ) -> Result<(), EscrowError> {
+        FundingHandler::request_adjustment(&env, &escrow_id, &caller, new_amount)
+    }
+
+    /// Approve the open adjustment request (depositor only), paying in a top-up or
+    /// receiving back a reduction.
+    pub fn approve_adjustment(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        FundingHandler::approve_adjustment(&env, &escrow_id, &caller)
+    }
+
+    /// Drop the open adjustment request (depositor rejects or beneficiary withdraws).
+    pub fn reject_adjustment(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        FundingHandler::reject_adjustment(&env, &escrow_id, &caller)
+    }
+
+    /// Replace the default 2-of-3 signer set of a Pending escrow with
+    /// weighted `signers` and a release `threshold`.
+    /// Requires authorization from the beneficiary and the depositor.
+    pub fn set_signers(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        signers: Vec<Signer>,
+        threshold: u32,
+    ) -> Result<(), EscrowError> {
+        EscrowHandler::set_signers(&env, &escrow_id, &caller, signers, threshold)
+    }
+
+    /// Get the signer set and threshold governing an escrow's release.
+    pub fn get_signers(env: Env, escrow_id: BytesN<32>) -> Result<SignerSet, EscrowError> {
+        EscrowHandler::get_signers(&env, &escrow_id)
+    }
+
+    /// Set how long approvals on a Pending escrow stay live, in seconds (0 = never expire).
+    /// Requires authorization from the beneficiary and the depositor.
+    pub fn set_approval_ttl(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        ttl: u64,
+    ) -> Result<(), EscrowError> {
+        EscrowHandler::set_approval_ttl(&env, &escrow_id, &caller, ttl)
+    }
+
+    /// Set the time after which an uncontested deposit can be refunded.
+    /// Requires authorization from the beneficiary and the depositor.
+    pub fn set_release_after(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        release_after: u64,
+    ) -> Result<(), EscrowError> {
+        EscrowHandler::set_release_after(&env, &escrow_id, &caller, release_after)
+    }
+
+    /// Refund the deposit to the depositor once the release-after time has passed
+    /// without a dispute or deduction claim, or a partly funded escrow once an
+    /// installment is overdue. Callable by anyone.
+    pub fn trigger_refund(env: Env, escrow_id: BytesN<32>) -> Result<(), EscrowError> {
+        EscrowHandler::trigger_refund(&env, &escrow_id)
+    }
+
+    /// Approve releasing a Funded escrow according to `split`.
+    /// Requires authorization from the approving party.
+    pub fn approve_release(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        EscrowHandler::approve_release(&env, &escrow_id, &caller, split)
+    }
+
+    /// Withdraw a live approval of `split`.
+    /// Requires authorization from the approving party.
+    pub fn revoke_approval(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        EscrowHandler::revoke_approval(&env, &escrow_id, &caller, split)
+    }
+
+    /// Freeze a Funded escrow pending arbiter resolution.
+    /// Requires authorization from the depositor or beneficiary.
+    pub fn initiate_dispute(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        category: DisputeCategory,
+        reason: String,
+    ) -> Result<(), EscrowError> {
+        DisputeHandler::initiate_dispute(&env, &escrow_id, &caller, category, reason)
+    }
+
+    /// Attach evidence to a Disputed escrow.
+    /// Requires authorization from the depositor or beneficiary.
+    pub fn submit_evidence(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        content_hash: BytesN<32>,
+        uri: String,
+    ) -> Result<(), EscrowError> {
+        DisputeHandler::submit_evidence(&env, &escrow_id, &caller, content_hash, uri)
+    }
+
+    /// Hand a dispute left unresolved past its ruling deadline to the admin.
+    /// Requires authorization from the depositor or beneficiary.
+    pub fn escalate_dispute(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        DisputeHandler::escalate_dispute(&env, &escrow_id, &caller)
+    }
+
+    /// Propose a split that settles a dispute without the arbiter.
+    /// Requires authorization from the depositor or beneficiary.
+    pub fn propose_settlement(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        DisputeHandler::propose_settlement(&env, &escrow_id, &caller, split)
+    }
+
+    /// Accept the other party's settlement offer and pay out.
+    /// Requires authorization from the depositor or beneficiary that did not propose it.
+    pub fn accept_settlement(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        DisputeHandler::accept_settlement(&env, &escrow_id, &caller, split)
+    }
+
+    /// Withdraw a dispute and return the escrow to Funded with approvals cleared.
+    /// Requires authorization from the party that opened the dispute.
+    pub fn withdraw_dispute(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        DisputeHandler::withdraw_dispute(&env, &escrow_id, &caller)
+    }
+
+    /// Get the open settlement offer on an escrow's dispute, if any.
+    pub fn get_settlement_offer(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Option<SettlementOffer>, EscrowError> {
+        DisputeHandler::get_settlement_offer(&env, &escrow_id)
+    }
+
+    /// Name a second-tier arbiter for a Pending escrow, making arbiter rulings appealable.
+    /// Requires authorization from the beneficiary and the depositor.
+    pub fn set_appeal_arbiter(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        appeal_arbiter: Address,
+    ) -> Result<(), EscrowError> {
+        AppealHandler::set_appeal_arbiter(&env, &escrow_id, &caller, &appeal_arbiter)
+    }
+
+    /// Appeal a provisional arbiter ruling by posting a bond.
+    /// Requires authorization from the depositor or beneficiary.
+    pub fn appeal_ruling(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        AppealHandler::appeal_ruling(&env, &escrow_id, &caller)
+    }
+
+    /// Settle an unappealed provisional ruling once its appeal window has closed, or an
+    /// appealed one the appeal arbiter did not decide within the dispute ruling window.
+    pub fn finalize_ruling(env: Env, escrow_id: BytesN<32>) -> Result<(), EscrowError> {
+        AppealHandler::finalize_ruling(&env, &escrow_id)
+    }
+
+    /// Decide an appealed ruling with a final split.
+    /// Requires authorization from the escrow's appeal arbiter.
+    pub fn decide_appeal(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        AppealHandler::decide_appeal(&env, &escrow_id, &caller, split)
+    }
+
+    /// Get the provisional ruling on an escrow's dispute, if any.
+    pub fn get_ruling(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Option<ProvisionalRuling>, EscrowError> {
+        AppealHandler::get_ruling(&env, &escrow_id)
+    }
+
+    /// Get the appeal filed against an escrow's provisional ruling, if any.
+    pub fn get_appeal(env: Env, escrow_id: BytesN<32>) -> Result<Option<Appeal>, EscrowError> {
+        AppealHandler::get_appeal(&env, &escrow_id)
+    }
+
+    /// Set the arbiter registry's bond token, minimum bond and ruling fee.
+    /// Requires authorization from the admin.
+    pub fn configure_arbiter_registry(
+        env: Env,
+        config: ArbiterRegistryConfig,
+    ) -> Result<(), EscrowError> {
+        ArbiterRegistry::configure(&env, config)
+    }
+
+    /// Register as an arbiter by staking `bond` of the registry's bond token.
+    /// Requires authorization from the arbiter.
+    pub fn register_arbiter(env: Env, arbiter: Address, bond: i128) -> Result<(), EscrowError> {
+        ArbiterRegistry::register(&env, &arbiter, bond)
+    }
+
+    /// Leave the arbiter registry; the bond can be withdrawn after the unbonding period.
+    /// Requires authorization from the arbiter.
+    pub fn unregister_arbiter(env: Env, arbiter: Address) -> Result<(), EscrowError> {
+        ArbiterRegistry::unregister(&env, &arbiter)
+    }
+
+    /// Withdraw an unregistered arbiter's bond once the unbonding period has passed.
+    /// Requires authorization from the arbiter.
+    pub fn withdraw_arbiter_bond(env: Env, arbiter: Address) -> Result<(), EscrowError> {
+        ArbiterRegistry::withdraw_bond(&env, &arbiter)
+    }
+
+    /// Activate or deactivate a registered arbiter. Requires authorization from the admin.
+    pub fn set_arbiter_active(env: Env, arbiter: Address, active: bool) -> Result<(), EscrowError> {
+        ArbiterRegistry::set_active(&env, &arbiter, active)
+    }
+
+    /// Slash part of an arbiter's bond to `recipient`. Requires authorization from the admin.
+    pub fn slash_arbiter(
+        env: Env,
+        arbiter: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> Result<(), EscrowError> {
+        ArbiterRegistry::slash(&env, &arbiter, amount, &recipient)
+    }
+
+    /// Get a registered arbiter's bond and status.
+    pub fn get_arbiter(env: Env, arbiter: Address) -> Result<ArbiterProfile, EscrowError> {
+        ArbiterRegistry::get_profile(&env, &arbiter)
+    }
+
+    /// Get an arbiter's ruling statistics; they outlive its registration.
+    pub fn get_arbiter_stats(env: Env, arbiter: Address) -> ArbiterStats {
+        ArbiterRegistry::get_stats(&env, &arbiter)
+    }
+
+    /// Get the share of an arbiter's appealed rulings that were reversed, in basis points.
+    pub fn get_arbiter_reversal_rate(env: Env, arbiter: Address) -> Result<u32, EscrowError> {
+        ArbiterRegistry::get_reversal_rate_bps(&env, &arbiter)
+    }
+
+    /// Add an arbiter to the panel pool. Requires authorization from the admin.
+    pub fn add_pool_arbiter(env: Env, arbiter: Address) -> Result<(), EscrowError> {
+        PanelHandler::add_pool_arbiter(&env, &arbiter)
+    }
+
+    /// Remove an arbiter from the panel pool. Requires authorization from the admin.
+    pub fn remove_pool_arbiter(env: Env, arbiter: Address) -> Result<(), EscrowError> {
+        PanelHandler::remove_pool_arbiter(&env, &arbiter)
+    }
+
+    /// Get the arbiters eligible to sit on dispute panels.
+    pub fn get_arbiter_pool(env: Env) -> Vec<Address> {
+        EscrowStorage::get_arbiter_pool(&env)
+    }
+
+    /// Have disputes on a Pending escrow decided by a panel of `size` pool arbiters.
+    /// Requires authorization from the beneficiary and the depositor.
+    pub fn set_panel_size(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        size: u32,
+    ) -> Result<(), EscrowError> {
+        PanelHandler::set_panel_size(&env, &escrow_id, &caller, size)
+    }
+
+    /// Vote on the split of a disputed escrow; the ruling applies once a majority agrees.
+    /// Requires authorization from a panelist.
+    pub fn cast_panel_vote(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        PanelHandler::cast_vote(&env, &escrow_id, &caller, split)
+    }
+
+    /// Replace a panelist who did not vote by the ruling deadline.
+    pub fn replace_panelist(
+        env: Env,
+        escrow_id: BytesN<32>,
+        panelist: Address,
+    ) -> Result<(), EscrowError> {
+        PanelHandler::replace_panelist(&env, &escrow_id, &panelist)
+    }
+
+    /// Get the panel assigned to an escrow's dispute, if any.
+    pub fn get_panel(env: Env, escrow_id: BytesN<32>) -> Result<Option<Panel>, EscrowError> {
+        PanelHandler::get_panel(&env, &escrow_id)
+    }
+
+    /// Resolve a Disputed escrow with a split ruling.
+    /// Rulings on escrows with an appeal arbiter are provisional until the appeal window closes.
+    /// Requires authorization from the arbiter, or the admin once escalated.
+    pub fn resolve_dispute(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        DisputeHandler::resolve_dispute(&env, &escrow_id, &caller, split)
+    }
+
+    /// File an itemized deduction claim against a Funded escrow.
+    /// Requires authorization from the beneficiary.
+    pub fn file_deduction_claim(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        items: Vec<DeductionItem>,
+    ) -> Result<(), EscrowError> {
+        DeductionHandler::file_claim(&env, &escrow_id, &caller, items)
+    }
+
+    /// Accept the listed claim lines and contest the rest.
+    /// Requires authorization from the depositor.
+    pub fn respond_to_claim(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        accepted_lines: Vec<u32>,
+    ) -> Result<(), EscrowError> {
+        DeductionHandler::respond_to_claim(&env, &escrow_id, &caller, accepted_lines)
+    }
+
+    /// Settle a claim left unanswered past its response window as fully accepted.
+    pub fn settle_unanswered_claim(env: Env, escrow_id: BytesN<32>) -> Result<(), EscrowError> {
+        DeductionHandler::settle_unanswered_claim(&env, &escrow_id)
+    }
+
+    /// Get the uncontested part of a deduction claim released ahead of the dispute
+    /// over the contested lines, if any.
+    pub fn get_partial_release(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Option<Settlement>, EscrowError> {
+        DeductionHandler::get_partial_release(&env, &escrow_id)
+    }
+
+    /// Get the deduction claim filed against an escrow, if any.
+    pub fn get_deduction_claim(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Option<DeductionClaim>, EscrowError> {
+        DeductionHandler::get_claim(&env, &escrow_id)
+    }
+
+    /// Get details of an escrow.
+    pub fn get_escrow(env: Env, escrow_id: BytesN<32>) -> Result<Escrow, EscrowError> {
+        EscrowHandler::get_escrow(&env, &escrow_id)
+    }
+
+    /// Get the escrow securing a rent agreement.
+    pub fn get_escrow_by_agreement(env: Env, agreement_id: String) -> Result<Escrow, EscrowError> {
+        EscrowHandler::get_escrow_by_agreement(&env, &agreement_id)
+    }
+
+    /// Get the split and resolution path of a settled escrow.
+    /// For a milestone escrow, each share is summed over all milestones and the path
+    /// is that of the last milestone.
+    pub fn get_settlement(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Option<Settlement>, EscrowError> {
+        EscrowHandler::get_settlement(&env, &escrow_id)
+    }
+
+    /// Get the installment schedule of an escrow; empty when it is funded in one go.
+    pub fn get_installments(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Vec<Installment>, EscrowError> {
+        FundingHandler::get_installments(&env, &escrow_id)
+    }
+
+    /// Get the open adjustment request on an escrow, if any.
+    pub fn get_adjustment(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Option<AdjustmentRequest>, EscrowError> {
+        FundingHandler::get_adjustment(&env, &escrow_id)
+    }
+
+    /// Get the yield policy of an escrow, if it has one.
+    pub fn get_yield_policy(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Option<YieldPolicy>, EscrowError> {
+        YieldVault::get_yield_policy(&env, &escrow_id)
+    }
+
+    /// Get the principal an escrow lost in its yield vault (0 if none).
+    pub fn get_vault_loss(env: Env, escrow_id: BytesN<32>) -> Result<i128, EscrowError> {
+        YieldVault::get_vault_loss(&env, &escrow_id)
+    }
+
+    /// Get the funds an escrow currently has in its yield vault, if any.
+    pub fn get_vault_position(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Option<VaultPosition>, EscrowError> {
+        YieldVault::get_vault_position(&env, &escrow_id)
+    }
+
+    /// List the escrows `party` takes part in as depositor, beneficiary or arbiter,
+    /// oldest first. Pass cursor 0 for the first page, then each page's `next_cursor`;
+    /// `limit` is capped at 50.
+    pub fn get_escrows_by_party(env: Env, party: Address, cursor: u32, limit: u32) -> EscrowPage {
+        EscrowQuery::by_party(&env, &party, cursor, limit)
+    }
+
+    /// List the escrows currently in `status`, paginated like `get_escrows_by_party`.
+    pub fn get_escrows_by_status(
+        env: Env,
+        status: EscrowStatus,
+        cursor: u32,
+        limit: u32,
+    ) -> EscrowPage {
+        EscrowQuery::by_status(&env, &status, cursor, limit)
+    }
+
+    /// List the escrows of `party` currently in `status`, e.g. the open disputes of an
+    /// arbiter. The cursor walks the party's escrows, so pages can come back short.
+    pub fn get_party_escrows_by_status(
+        env: Env,
+        party: Address,
+        status: EscrowStatus,
+        cursor: u32,
+        limit: u32,
+    ) -> EscrowPage {
+        EscrowQuery::by_party_and_status(&env, &party, &status, cursor, limit)
+    }
+
+    /// Get the milestones of an escrow; empty for a plain escrow.
+    pub fn get_milestones(env: Env, escrow_id: BytesN<32>) -> Result<Vec<Milestone>, EscrowError> {
+        MilestoneHandler::get_milestones(&env, &escrow_id)
+    }
+
+    /// Get the number of milestones already settled, i.e. the index of the current one.
+    pub fn get_milestones_settled(env: Env, escrow_id: BytesN<32>) -> Result<u32, EscrowError> {
+        MilestoneHandler::get_milestones_settled(&env, &escrow_id)
+    }
+
+    /// Get the split and resolution path of one settled milestone.
+    pub fn get_milestone_settlement(
+        env: Env,
+        escrow_id: BytesN<32>,
+        index: u32,
+    ) -> Result<Option<Settlement>, EscrowError> {
+        MilestoneHandler::get_milestone_settlement(&env, &escrow_id, index)
+    }
+
+    /// Get the time after which an uncontested deposit can be refunded (0 if unset).
+    pub fn get_release_after(env: Env, escrow_id: BytesN<32>) -> Result<u64, EscrowError> {
+        EscrowHandler::get_release_after(&env, &escrow_id)
+    }
+
+    /// Get the approvals on an escrow that have not expired or been revoked.
+    pub fn get_approvals(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Vec<ReleaseApproval>, EscrowError> {
+        EscrowHandler::get_approvals(&env, &escrow_id)
+    }
+
+    /// Get the approving weight for exactly `split` and the threshold it must reach.
+    pub fn get_approval_count(
+        env: Env,
+        escrow_id: BytesN<32>,
+        split: ReleaseSplit,
+    ) -> Result<ApprovalProgress, EscrowError> {
+        EscrowHandler::get_approval_count(&env, &escrow_id, &split)
+    }
+
+    /// Get the dispute record for an escrow, if any.
+    pub fn get_dispute_info(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<Option<Dispute>, EscrowError> {
+        DisputeHandler::get_dispute_info(&env, &escrow_id)
+    }
+
+    /// Check if an escrow is currently disputed.
+    pub fn is_disputed(env: Env, escrow_id: BytesN<32>) -> Result<bool, EscrowError> {
+        DisputeHandler::is_disputed(&env, &escrow_id)
+    }
+
+    /// Get the total amount of `token` held across funded escrows.
+    pub fn get_total_held(env: Env, token: Address) -> i128 {
+        EscrowStorage::get_total_held(&env, &token)
+    }
+
+    /// Get the total number of escrows created.
+    pub fn get_escrow_count(env: Env) -> u32 {
+        EscrowStorage::get_count(&env)
+    }
+}
+
+impl EscrowContract {
+    fn validate_config(config: &EscrowConfig) -> Result<(), EscrowError> {
+        if config.dispute_response_window == 0
+            || config.dispute_ruling_window == 0
+            || config.appeal_window == 0
+            || config.appeal_bond_bps > 10_000
+        {
+            return Err(EscrowError::InvalidConfig);
+        }
+        Ok(())
+    }
+}
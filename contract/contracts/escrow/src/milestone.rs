@@ -0,0 +1,147 @@
+//! Milestone escrows for the Escrow contract.
+//! A milestone escrow is funded once for the sum of its milestones and released one
+//! milestone at a time, in order. Each milestone goes through the usual approval and
+//! dispute flow against its own amount; the escrow reaches a final status once the
+//! last milestone is settled.
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
+
+use crate::dispute::DisputeHandler;
+use crate::errors::EscrowError;
+use crate::escrow_impl::EscrowHandler;
+use crate::storage::EscrowStorage;
+use crate::types::{Escrow, Milestone, ReleaseSplit, Settlement};
+
+/// Milestone escrow handling.
+pub struct MilestoneHandler;
+
+impl MilestoneHandler {
+    /// Create an escrow released in milestones.
+    ///
+    /// CHECKS:
+    /// - Same checks as `EscrowHandler::create`
+    /// - At least one milestone, every amount positive, due dates in order
+    ///
+    /// EFFECTS:
+    /// - Create a Pending escrow for the sum of the milestone amounts
+    /// - Store the milestones
+    pub fn create(
+        env: &Env,
+        agreement_id: String,
+        depositor: Address,
+        beneficiary: Address,
+        arbiter: Address,
+        token: Address,
+        milestones: Vec<Milestone>,
+    ) -> Result<BytesN<32>, EscrowError> {
+        // CHECKS: Validate the milestones
+        if milestones.is_empty() {
+            return Err(EscrowError::InvalidMilestones);
+        }
+
+        let mut total: i128 = 0;
+        let mut last_due = 0;
+        for milestone in &milestones {
+            if milestone.amount <= 0 || milestone.due_date < last_due {
+                return Err(EscrowError::InvalidMilestones);
+            }
+            total = total
+                .checked_add(milestone.amount)
+                .ok_or(EscrowError::InvalidMilestones)?;
+            last_due = milestone.due_date;
+        }
+
+        // EFFECTS: Create the parent escrow, then attach the milestones
+        let escrow_id = EscrowHandler::create(
+            env,
+            agreement_id,
+            depositor,
+            beneficiary,
+            arbiter,
+            total,
+            token,
+        )?;
+        EscrowStorage::save_milestones(env, &escrow_id, &milestones);
+
+        Ok(escrow_id)
+    }
+
+    /// The milestone currently up for release and its index, if the escrow has one.
+    pub fn current(env: &Env, escrow: &Escrow) -> Option<(u32, Milestone)> {
+        let milestones = EscrowStorage::get_milestones(env, &escrow.id)?;
+        let index = EscrowStorage::get_milestones_settled(env, &escrow.id);
+        milestones.get(index).map(|milestone| (index, milestone))
+    }
+
+    /// Record the settlement of the current milestone and move on to the next one,
+    /// dropping the finished milestone's dispute state.
+    ///
+    /// Returns the escrow's overall settlement (each share summed over all milestones)
+    /// once the last milestone is settled, None while milestones remain.
+    pub fn record_settlement(
+        env: &Env,
+        escrow: &mut Escrow,
+        index: u32,
+        milestone: &Milestone,
+        settlement: &Settlement,
+    ) -> Option<Settlement> {
+        EscrowStorage::save_milestone_settlement(env, &escrow.id, index, settlement);
+        escrow.amount -= milestone.amount;
+        escrow.funded -= milestone.amount;
+
+        let count = EscrowStorage::get_milestones(env, &escrow.id)
+            .map(|milestones| milestones.len())
+            .unwrap_or_default();
+        if index + 1 < count {
+            DisputeHandler::close(env, escrow);
+            EscrowStorage::clear_offer(env, &escrow.id);
+            EscrowStorage::clear_ruling(env, &escrow.id);
+            EscrowStorage::clear_panel(env, &escrow.id);
+            return None;
+        }
+
+        let mut total = ReleaseSplit {
+            beneficiary_amount: 0,
+            depositor_amount: 0,
+            arbiter_fee: 0,
+        };
+        for i in 0..count {
+            if let Some(paid) = EscrowStorage::get_milestone_settlement(env, &escrow.id, i) {
+                total.beneficiary_amount += paid.split.beneficiary_amount;
+                total.depositor_amount += paid.split.depositor_amount;
+                total.arbiter_fee += paid.split.arbiter_fee;
+            }
+        }
+        Some(Settlement {
+            split: total,
+            path: settlement.path.clone(),
+            settled_at: settlement.settled_at,
+        })
+    }
+
+    /// Get the milestones of an escrow; empty for a plain escrow.
+    pub fn get_milestones(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+    ) -> Result<Vec<Milestone>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_milestones(env, escrow_id).unwrap_or(Vec::new(env)))
+    }
+
+    /// Get the number of milestones already settled.
+    pub fn get_milestones_settled(env: &Env, escrow_id: &BytesN<32>) -> Result<u32, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_milestones_settled(env, escrow_id))
+    }
+
+    /// Get the payout record of one milestone, if settled.
+    pub fn get_milestone_settlement(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        index: u32,
+    ) -> Result<Option<Settlement>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_milestone_settlement(
+            env, escrow_id, index,
+        ))
+    }
+}
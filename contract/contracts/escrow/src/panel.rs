@@ -0,0 +1,287 @@
+//! Arbiter panels for the Escrow contract.
+//! The admin keeps a pool of registered arbiters. An escrow can ask for its disputes to be
+//! decided by a panel drawn from that pool with a PRNG seeded per dispute;
+//! the ruling is applied once a majority of panelists vote for the same split.
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec};
+
+use crate::access::AccessControl;
+use crate::dispute::DisputeHandler;
+use crate::errors::EscrowError;
+use crate::escrow_impl::EscrowHandler;
+use crate::registry::ArbiterRegistry;
+use crate::storage::EscrowStorage;
+use crate::types::{Dispute, Escrow, EscrowStatus, Panel, PanelVote, ReleaseSplit, ResolutionPath};
+
+/// Arbiter pool and panel handling.
+pub struct PanelHandler;
+
+impl PanelHandler {
+    /// Add a registered, active arbiter to the pool (admin only).
+    pub fn add_pool_arbiter(env: &Env, arbiter: &Address) -> Result<(), EscrowError> {
+        let state = EscrowStorage::get_state(env).ok_or(EscrowError::NotInitialized)?;
+        state.admin.require_auth();
+
+        ArbiterRegistry::require_active(env, arbiter)?;
+
+        let mut pool = EscrowStorage::get_arbiter_pool(env);
+        if pool.contains(arbiter) {
+            return Err(EscrowError::ArbiterInPool);
+        }
+
+        pool.push_back(arbiter.clone());
+        EscrowStorage::save_arbiter_pool(env, &pool);
+
+        Ok(())
+    }
+
+    /// Remove an arbiter from the pool (admin only).
+    /// Panels already drawn keep the arbiter until it is replaced.
+    pub fn remove_pool_arbiter(env: &Env, arbiter: &Address) -> Result<(), EscrowError> {
+        let state = EscrowStorage::get_state(env).ok_or(EscrowError::NotInitialized)?;
+        state.admin.require_auth();
+
+        let mut pool = EscrowStorage::get_arbiter_pool(env);
+        let position = pool
+            .first_index_of(arbiter)
+            .ok_or(EscrowError::ArbiterNotInPool)?;
+
+        pool.remove(position);
+        EscrowStorage::save_arbiter_pool(env, &pool);
+
+        Ok(())
+    }
+
+    /// Ask for disputes on a Pending escrow to be decided by a panel of `size` arbiters.
+    /// The depositor must co-sign, since the panel decides how its deposit is split.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the beneficiary
+    /// - Depositor must authorize
+    /// - Escrow must be in Pending state
+    /// - Size must be odd so a majority always exists
+    /// - Pool must hold at least `size` arbiters outside the escrow parties
+    pub fn set_panel_size(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        size: u32,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_beneficiary(&escrow, caller)?;
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Pending {
+            return Err(EscrowError::InvalidState);
+        }
+
+        if size.is_multiple_of(2) {
+            return Err(EscrowError::InvalidConfig);
+        }
+
+        if Self::eligible(env, &escrow, &Vec::new(env)).len() < size {
+            return Err(EscrowError::PoolTooSmall);
+        }
+
+        // EFFECTS: Store the panel size
+        EscrowStorage::set_panel_size(env, escrow_id, size);
+
+        Ok(())
+    }
+
+    /// Draw a panel for a newly opened dispute, if the escrow asked for one.
+    pub fn assign_panel(env: &Env, escrow: &Escrow, dispute: &Dispute) -> Result<(), EscrowError> {
+        let size = EscrowStorage::get_panel_size(env, &escrow.id);
+        if size == 0 {
+            return Ok(());
+        }
+
+        let mut panel = Panel {
+            members: Vec::new(env),
+            votes: Vec::new(env),
+            draws: 0,
+        };
+        panel.members = Self::draw(env, escrow, dispute, &mut panel, size)?;
+        EscrowStorage::save_panel(env, &escrow.id, &panel);
+
+        Ok(())
+    }
+
+    /// Vote on the split of a disputed escrow as a panelist.
+    /// Once a majority of the panel votes for the same split, the ruling is applied
+    /// exactly as an arbiter's `resolve_dispute` would be. Panels rule without a fee,
+    /// since the escrow's arbiter did not decide the dispute.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and sit on the dispute's panel
+    /// - Escrow must be Disputed and not escalated
+    /// - Caller must not have voted already
+    /// - Counter-party must have submitted evidence or had its full response window
+    /// - Split must be non-negative, carry no arbiter fee and add up to the escrowed amount
+    ///
+    /// EFFECTS:
+    /// - Record the vote; on a majority, record or apply the ruling (path Panel)
+    pub fn cast_vote(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        split: ReleaseSplit,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let mut escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let dispute =
+            EscrowStorage::get_dispute(env, escrow_id).ok_or(EscrowError::InvalidState)?;
+        if dispute.escalated {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let mut panel = EscrowStorage::get_panel(env, escrow_id).ok_or(EscrowError::NotPanelist)?;
+        if !panel.members.contains(caller) {
+            return Err(EscrowError::NotPanelist);
+        }
+        if Self::has_voted(&panel, caller) {
+            return Err(EscrowError::AlreadyVoted);
+        }
+
+        DisputeHandler::check_ruling_allowed(env, &escrow, &dispute)?;
+        if split.arbiter_fee != 0 {
+            return Err(EscrowError::InvalidArbiterFee);
+        }
+        EscrowHandler::validate_split(env, &escrow, &split)?;
+
+        // EFFECTS: Record the vote
+        panel.votes.push_back(PanelVote {
+            panelist: caller.clone(),
+            split: split.clone(),
+            voted_at: env.ledger().timestamp(),
+        });
+        EscrowStorage::save_panel(env, escrow_id, &panel);
+
+        // Apply the ruling once a majority agrees
+        let in_favor = panel
+            .votes
+            .iter()
+            .filter(|vote| vote.split == split)
+            .count() as u32;
+        if in_favor > panel.members.len() / 2 {
+            DisputeHandler::apply_ruling(env, &mut escrow, caller, split, ResolutionPath::Panel)?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace a panelist who has not voted by the dispute's ruling deadline.
+    /// Anyone may call this. The ruling deadline restarts so the new panelist gets
+    /// a full ruling window.
+    ///
+    /// CHECKS:
+    /// - Escrow must be Disputed, not escalated and without a pending ruling
+    /// - `panelist` must sit on the panel and not have voted
+    /// - Ruling deadline must have passed
+    /// - Pool must hold an eligible arbiter not already on the panel
+    pub fn replace_panelist(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        panelist: &Address,
+    ) -> Result<(), EscrowError> {
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let mut dispute =
+            EscrowStorage::get_dispute(env, escrow_id).ok_or(EscrowError::InvalidState)?;
+        if dispute.escalated {
+            return Err(EscrowError::InvalidState);
+        }
+        if EscrowStorage::get_ruling(env, escrow_id).is_some() {
+            return Err(EscrowError::RulingPending);
+        }
+
+        let mut panel = EscrowStorage::get_panel(env, escrow_id).ok_or(EscrowError::NotPanelist)?;
+        let position = panel
+            .members
+            .first_index_of(panelist)
+            .ok_or(EscrowError::NotPanelist)?;
+        if Self::has_voted(&panel, panelist) {
+            return Err(EscrowError::AlreadyVoted);
+        }
+
+        let now = env.ledger().timestamp();
+        if now <= dispute.ruling_deadline {
+            return Err(EscrowError::RulingWindowOpen);
+        }
+
+        let replacement = Self::draw(env, &escrow, &dispute, &mut panel, 1)?;
+
+        // EFFECTS: Swap in the replacement and restart the ruling window
+        let replacement = replacement.get_unchecked(0);
+        ArbiterRegistry::unassign(env, panelist);
+        ArbiterRegistry::assign(env, &replacement);
+        panel.members.set(position, replacement);
+        EscrowStorage::save_panel(env, escrow_id, &panel);
+
+        dispute.ruling_deadline = now + EscrowStorage::get_config(env).dispute_ruling_window;
+        EscrowStorage::save_dispute(env, escrow_id, &dispute);
+
+        Ok(())
+    }
+
+    /// Get the panel assigned to an escrow's dispute, if any.
+    pub fn get_panel(env: &Env, escrow_id: &BytesN<32>) -> Result<Option<Panel>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_panel(env, escrow_id))
+    }
+
+    fn has_voted(panel: &Panel, panelist: &Address) -> bool {
+        panel.votes.iter().any(|vote| vote.panelist == *panelist)
+    }
+
+    /// Active pool arbiters that are neither escrow parties nor already in `exclude`.
+    fn eligible(env: &Env, escrow: &Escrow, exclude: &Vec<Address>) -> Vec<Address> {
+        let mut eligible = Vec::new(env);
+        for arbiter in EscrowStorage::get_arbiter_pool(env) {
+            if AccessControl::is_party(escrow, &arbiter).is_err()
+                && !exclude.contains(&arbiter)
+                && ArbiterRegistry::is_active(env, &arbiter)
+            {
+                eligible.push_back(arbiter);
+            }
+        }
+        eligible
+    }
+
+    /// Draw `count` eligible arbiters not already on the panel.
+    /// The PRNG is seeded with sha256(escrow_id || opened_at || draw number), so each
+    /// dispute gets its own deterministic draw.
+    fn draw(
+        env: &Env,
+        escrow: &Escrow,
+        dispute: &Dispute,
+        panel: &mut Panel,
+        count: u32,
+    ) -> Result<Vec<Address>, EscrowError> {
+        let mut candidates = Self::eligible(env, escrow, &panel.members);
+        if candidates.len() < count {
+            return Err(EscrowError::PoolTooSmall);
+        }
+
+        let mut seed = Bytes::from_array(env, &escrow.id.to_array());
+        seed.extend_from_array(&dispute.opened_at.to_be_bytes());
+        seed.extend_from_array(&panel.draws.to_be_bytes());
+        env.prng()
+            .seed(env.crypto().sha256(&seed).to_bytes().into());
+        env.prng().shuffle(&mut candidates);
+        panel.draws += 1;
+
+        Ok(candidates.slice(0..count))
+    }
+}
@@ -0,0 +1,79 @@
+//! Escrow queries for the Escrow contract.
+//! Lists escrows by party and by status from the indexes `EscrowStorage::save`
+//! maintains, one page at a time. Each index entry is its own storage key, so a
+//! page only reads the entries it returns. A cursor is a position in the index:
+//! pass 0 for the first page, then each page's `next_cursor`.
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use crate::storage::EscrowStorage;
+use crate::types::{EscrowPage, EscrowStatus};
+
+/// Largest page a query returns; bigger limits are capped.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Paginated escrow listings.
+pub struct EscrowQuery;
+
+impl EscrowQuery {
+    /// Escrows `party` takes part in as depositor, beneficiary or arbiter, oldest first.
+    pub fn by_party(env: &Env, party: &Address, cursor: u32, limit: u32) -> EscrowPage {
+        let count = EscrowStorage::get_party_escrow_count(env, party);
+        Self::page(env, count, cursor, limit, None, |position| {
+            EscrowStorage::get_party_escrow(env, party, position)
+        })
+    }
+
+    /// Escrows currently in `status`, in the order they entered it until one leaves.
+    /// An escrow leaving the status has the last entry moved into its place, so a
+    /// listing that spans a transition can miss or repeat that entry.
+    pub fn by_status(env: &Env, status: &EscrowStatus, cursor: u32, limit: u32) -> EscrowPage {
+        let count = EscrowStorage::get_status_escrow_count(env, status);
+        Self::page(env, count, cursor, limit, None, |position| {
+            EscrowStorage::get_status_escrow(env, status, position)
+        })
+    }
+
+    /// Escrows `party` takes part in that are currently in `status`, oldest first.
+    /// The cursor walks the party's index, so a page may hold fewer than `limit`
+    /// escrows while `next_cursor` is still set.
+    pub fn by_party_and_status(
+        env: &Env,
+        party: &Address,
+        status: &EscrowStatus,
+        cursor: u32,
+        limit: u32,
+    ) -> EscrowPage {
+        let count = EscrowStorage::get_party_escrow_count(env, party);
+        Self::page(env, count, cursor, limit, Some(status), |position| {
+            EscrowStorage::get_party_escrow(env, party, position)
+        })
+    }
+
+    /// Load up to `limit` escrows from an index of `count` entries starting at
+    /// `cursor`, keeping those in `status` if given. Scans at most `limit` index entries.
+    fn page(
+        env: &Env,
+        count: u32,
+        cursor: u32,
+        limit: u32,
+        status: Option<&EscrowStatus>,
+        id_at: impl Fn(u32) -> Option<BytesN<32>>,
+    ) -> EscrowPage {
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        let mut escrows = Vec::new(env);
+        for position in cursor..end {
+            let Some(escrow) = id_at(position).and_then(|id| EscrowStorage::get(env, &id)) else {
+                continue;
+            };
+            if status.is_none_or(|status| escrow.status == *status) {
+                escrows.push_back(escrow);
+            }
+        }
+
+        EscrowPage {
+            escrows,
+            next_cursor: (end < count).then_some(end),
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! Bonded arbiter registry for the Escrow contract.
+//! Arbiters stake a bond in the configured token before they can be named on an
+//! escrow, earn a fixed share of the disputed amount for each ruling, and can be
+//! slashed by the admin. An arbiter that unregisters can withdraw its bond once
+//! the unbonding period has passed and it no longer decides any open dispute.
+//! The registry also keeps per-arbiter ruling statistics, which outlive the profile.
+use soroban_sdk::{Address, Env};
+
+use crate::custody::Custody;
+use crate::errors::EscrowError;
+use crate::escrow_impl::EscrowHandler;
+use crate::storage::EscrowStorage;
+use crate::types::{ArbiterProfile, ArbiterRegistryConfig, ArbiterStats, Escrow, ReleaseSplit};
+
+/// Time between unregistering and withdrawing the bond (30 days), so the bond
+/// still backs rulings on disputes the arbiter took on before leaving.
+pub const UNBONDING_PERIOD: u64 = 2_592_000;
+
+/// Arbiter registration, fees, slashing and statistics.
+pub struct ArbiterRegistry;
+
+impl ArbiterRegistry {
+    /// Set the bond token, minimum bond and ruling fee (admin only).
+    pub fn configure(env: &Env, config: ArbiterRegistryConfig) -> Result<(), EscrowError> {
+        let state = EscrowStorage::get_state(env).ok_or(EscrowError::NotInitialized)?;
+        state.admin.require_auth();
+
+        if config.min_bond < 0 || config.fee_bps > 10_000 {
+            return Err(EscrowError::InvalidConfig);
+        }
+
+        EscrowStorage::save_registry_config(env, &config);
+
+        Ok(())
+    }
+
+    /// Register as an arbiter by staking `bond` of the registry's bond token.
+    ///
+    /// CHECKS:
+    /// - Arbiter must authorize
+    /// - Registry must be configured
+    /// - Arbiter must not already be registered
+    /// - Bond must be at least the configured minimum
+    ///
+    /// EFFECTS:
+    /// - Store an active profile
+    ///
+    /// INTERACTIONS:
+    /// - Transfer the bond into the contract
+    pub fn register(env: &Env, arbiter: &Address, bond: i128) -> Result<(), EscrowError> {
+        // CHECKS: Arbiter must authorize
+        arbiter.require_auth();
+
+        let config = EscrowStorage::get_registry_config(env).ok_or(EscrowError::NotInitialized)?;
+        if EscrowStorage::get_arbiter(env, arbiter).is_some() {
+            return Err(EscrowError::ArbiterAlreadyRegistered);
+        }
+        if bond < config.min_bond || bond <= 0 {
+            return Err(EscrowError::InsufficientBond);
+        }
+
+        // EFFECTS: Store the profile
+        let profile = ArbiterProfile {
+            bond,
+            active: true,
+            registered_at: env.ledger().timestamp(),
+            unbonds_at: 0,
+        };
+        EscrowStorage::save_arbiter(env, arbiter, &profile);
+
+        // INTERACTIONS: Take the bond into custody
+        Custody::deposit(env, &config.bond_token, arbiter, bond)
+    }
+
+    /// Leave the registry. The arbiter is deactivated right away and can withdraw
+    /// its bond once UNBONDING_PERIOD has passed.
+    ///
+    /// CHECKS:
+    /// - Arbiter must authorize and be registered
+    /// - Arbiter must not already be unbonding
+    ///
+    /// EFFECTS:
+    /// - Deactivate the arbiter and start the unbonding period
+    pub fn unregister(env: &Env, arbiter: &Address) -> Result<(), EscrowError> {
+        // CHECKS: Arbiter must authorize
+        arbiter.require_auth();
+
+        let mut profile =
+            EscrowStorage::get_arbiter(env, arbiter).ok_or(EscrowError::ArbiterNotRegistered)?;
+        if profile.unbonds_at != 0 {
+            return Err(EscrowError::InvalidState);
+        }
+
+        // EFFECTS: Deactivate and start unbonding
+        profile.active = false;
+        profile.unbonds_at = env.ledger().timestamp() + UNBONDING_PERIOD;
+        EscrowStorage::save_arbiter(env, arbiter, &profile);
+
+        Ok(())
+    }
+
+    /// Withdraw the bond of an unregistered arbiter and drop its profile.
+    /// Its ruling statistics are kept.
+    ///
+    /// CHECKS:
+    /// - Arbiter must authorize and be registered
+    /// - Arbiter must have unregistered and the unbonding period must have passed
+    /// - Arbiter must not decide any open dispute, alone or on a panel, so the
+    ///   bond is still there to slash
+    ///
+    /// EFFECTS:
+    /// - Remove the profile
+    ///
+    /// INTERACTIONS:
+    /// - Transfer the remaining bond back to the arbiter
+    pub fn withdraw_bond(env: &Env, arbiter: &Address) -> Result<(), EscrowError> {
+        // CHECKS: Arbiter must authorize
+        arbiter.require_auth();
+
+        let config = EscrowStorage::get_registry_config(env).ok_or(EscrowError::NotInitialized)?;
+        let profile =
+            EscrowStorage::get_arbiter(env, arbiter).ok_or(EscrowError::ArbiterNotRegistered)?;
+        if profile.unbonds_at == 0 {
+            return Err(EscrowError::InvalidState);
+        }
+        if env.ledger().timestamp() < profile.unbonds_at {
+            return Err(EscrowError::RefundNotDue);
+        }
+        if EscrowStorage::get_arbiter_assignments(env, arbiter) > 0 {
+            return Err(EscrowError::DisputeActive);
+        }
+
+        // EFFECTS: Drop the profile
+        EscrowStorage::remove_arbiter(env, arbiter);
+
+        // INTERACTIONS: Return the bond
+        if profile.bond > 0 {
+            Custody::pay_out(env, &config.bond_token, arbiter, profile.bond)?;
+        }
+
+        Ok(())
+    }
+
+    /// Activate or deactivate a registered arbiter (admin only).
+    /// Inactive arbiters cannot be named on new escrows or drawn onto panels.
+    /// An arbiter that has unregistered cannot be reactivated.
+    pub fn set_active(env: &Env, arbiter: &Address, active: bool) -> Result<(), EscrowError> {
+        let state = EscrowStorage::get_state(env).ok_or(EscrowError::NotInitialized)?;
+        state.admin.require_auth();
+
+        let mut profile =
+            EscrowStorage::get_arbiter(env, arbiter).ok_or(EscrowError::ArbiterNotRegistered)?;
+        if active && profile.unbonds_at != 0 {
+            return Err(EscrowError::InvalidState);
+        }
+        profile.active = active;
+        EscrowStorage::save_arbiter(env, arbiter, &profile);
+
+        Ok(())
+    }
+
+    /// Slash `amount` of an arbiter's bond to `recipient` (admin only), e.g. after a
+    /// successful appeal or proven misconduct. An arbiter left below the minimum bond
+    /// is deactivated.
+    ///
+    /// CHECKS:
+    /// - Admin must authorize
+    /// - Arbiter must be registered with at least `amount` bonded
+    ///
+    /// EFFECTS:
+    /// - Reduce the bond; deactivate if it falls below the minimum
+    ///
+    /// INTERACTIONS:
+    /// - Transfer the slashed amount to `recipient`
+    pub fn slash(
+        env: &Env,
+        arbiter: &Address,
+        amount: i128,
+        recipient: &Address,
+    ) -> Result<(), EscrowError> {
+        let state = EscrowStorage::get_state(env).ok_or(EscrowError::NotInitialized)?;
+        state.admin.require_auth();
+
+        let config = EscrowStorage::get_registry_config(env).ok_or(EscrowError::NotInitialized)?;
+        let mut profile =
+            EscrowStorage::get_arbiter(env, arbiter).ok_or(EscrowError::ArbiterNotRegistered)?;
+        if amount <= 0 || amount > profile.bond {
+            return Err(EscrowError::InsufficientBond);
+        }
+
+        // EFFECTS: Reduce the bond
+        profile.bond -= amount;
+        if profile.bond < config.min_bond {
+            profile.active = false;
+        }
+        EscrowStorage::save_arbiter(env, arbiter, &profile);
+
+        // INTERACTIONS: Pay out the slashed bond
+        Custody::pay_out(env, &config.bond_token, recipient, amount)
+    }
+
+    /// Verify an arbiter is registered and active.
+    pub fn require_active(env: &Env, arbiter: &Address) -> Result<(), EscrowError> {
+        let profile =
+            EscrowStorage::get_arbiter(env, arbiter).ok_or(EscrowError::ArbiterNotRegistered)?;
+        if !profile.active {
+            return Err(EscrowError::ArbiterInactive);
+        }
+        Ok(())
+    }
+
+    /// Whether an arbiter is registered and active.
+    pub fn is_active(env: &Env, arbiter: &Address) -> bool {
+        Self::require_active(env, arbiter).is_ok()
+    }
+
+    /// Verify a ruling paying the escrow's arbiter carries exactly the registry fee:
+    /// fee_bps of the disputed amount, rounded down.
+    pub fn check_fee(env: &Env, escrow: &Escrow, split: &ReleaseSplit) -> Result<(), EscrowError> {
+        let fee_bps = EscrowStorage::get_registry_config(env)
+            .map(|config| config.fee_bps)
+            .unwrap_or_default();
+        let disputed = EscrowHandler::current_amount(env, escrow);
+        if split.arbiter_fee != disputed * fee_bps as i128 / 10_000 {
+            return Err(EscrowError::InvalidArbiterFee);
+        }
+        Ok(())
+    }
+
+    /// Count an open dispute `arbiter` now decides.
+    pub fn assign(env: &Env, arbiter: &Address) {
+        let count = EscrowStorage::get_arbiter_assignments(env, arbiter);
+        EscrowStorage::set_arbiter_assignments(env, arbiter, count + 1);
+    }
+
+    /// Drop a dispute `arbiter` no longer decides.
+    pub fn unassign(env: &Env, arbiter: &Address) {
+        let count = EscrowStorage::get_arbiter_assignments(env, arbiter);
+        EscrowStorage::set_arbiter_assignments(env, arbiter, count.saturating_sub(1));
+    }
+
+    /// Count a ruling made by `arbiter`.
+    pub fn record_ruling(env: &Env, arbiter: &Address) {
+        let mut stats = EscrowStorage::get_arbiter_stats(env, arbiter);
+        stats.rulings += 1;
+        EscrowStorage::save_arbiter_stats(env, arbiter, &stats);
+    }
+
+    /// Count an appeal decided against a ruling by `arbiter`, and whether it was reversed.
+    pub fn record_appeal(env: &Env, arbiter: &Address, reversed: bool) {
+        let mut stats = EscrowStorage::get_arbiter_stats(env, arbiter);
+        stats.appeals_decided += 1;
+        if reversed {
+            stats.appeals_reversed += 1;
+        }
+        EscrowStorage::save_arbiter_stats(env, arbiter, &stats);
+    }
+
+    /// Get an arbiter's registry profile.
+    pub fn get_profile(env: &Env, arbiter: &Address) -> Result<ArbiterProfile, EscrowError> {
+        EscrowStorage::get_arbiter(env, arbiter).ok_or(EscrowError::ArbiterNotRegistered)
+    }
+
+    /// Get an arbiter's ruling statistics.
+    pub fn get_stats(env: &Env, arbiter: &Address) -> ArbiterStats {
+        EscrowStorage::get_arbiter_stats(env, arbiter)
+    }
+
+    /// Share of an arbiter's appealed rulings that were reversed, in basis points.
+    pub fn get_reversal_rate_bps(env: &Env, arbiter: &Address) -> Result<u32, EscrowError> {
+        let stats = EscrowStorage::get_arbiter_stats(env, arbiter);
+        if stats.appeals_decided == 0 {
+            return Ok(0);
+        }
+        Ok(stats.appeals_reversed * 10_000 / stats.appeals_decided)
+    }
+}
@@ -1,8 +1,22 @@
 //! Storage operations for the Escrow contract.
 //! Implements single-responsibility getter/setter helpers.
-use soroban_sdk::{BytesN, Env, Vec};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
-use crate::types::{DataKey, Escrow, ReleaseApproval};
+use crate::types::{
+    AdjustmentRequest, Appeal, ArbiterProfile, ArbiterRegistryConfig, ArbiterStats, ContractState,
+    DataKey, DeductionClaim, Dispute, Escrow, EscrowConfig, EscrowStatus, Installment, Milestone,
+    Panel, ProvisionalRuling, ReleaseApproval, Settlement, SettlementOffer, Signer, SignerSet,
+    VaultPosition, YieldPolicy,
+};
+
+/// Default counter-party response window for disputes (7 days).
+pub const DEFAULT_DISPUTE_RESPONSE_WINDOW: u64 = 604_800;
+/// Default arbiter ruling window for disputes (14 days).
+pub const DEFAULT_DISPUTE_RULING_WINDOW: u64 = 1_209_600;
+/// Default appeal window for provisional rulings (3 days).
+pub const DEFAULT_APPEAL_WINDOW: u64 = 259_200;
+/// Default appeal bond (10% of the disputed amount).
+pub const DEFAULT_APPEAL_BOND_BPS: u32 = 1_000;
 
 /// Escrow storage management.
 pub struct EscrowStorage;
@@ -16,10 +30,486 @@ impl EscrowStorage {
     }
 
     /// Save or update an escrow.
-    /// Updates existing escrow or creates a new one.
+    /// Updates existing escrow or creates a new one, keeping the party and status
+    /// indexes in step with it.
     pub fn save(env: &Env, escrow: &Escrow) {
         let key = DataKey::Escrow(escrow.id.clone());
+        let previous = env.storage().persistent().get::<_, Escrow>(&key);
         env.storage().persistent().set(&key, escrow);
+
+        match previous {
+            None => {
+                for party in [&escrow.depositor, &escrow.beneficiary, &escrow.arbiter] {
+                    Self::add_to_party(env, party, &escrow.id);
+                }
+                Self::add_to_status(env, &escrow.status, &escrow.id);
+            }
+            Some(previous) if previous.status != escrow.status => {
+                Self::remove_from_status(env, &previous.status, &escrow.id);
+                Self::add_to_status(env, &escrow.status, &escrow.id);
+            }
+            Some(_) => {}
+        }
+    }
+
+    /// Number of escrows a party takes part in.
+    pub fn get_party_escrow_count(env: &Env, party: &Address) -> u32 {
+        let key = DataKey::PartyEscrowCount(party.clone());
+        env.storage()
+            .persistent()
+            .get::<_, u32>(&key)
+            .unwrap_or_default()
+    }
+
+    /// ID of the escrow at `position` in a party's index, oldest first.
+    pub fn get_party_escrow(env: &Env, party: &Address, position: u32) -> Option<BytesN<32>> {
+        let key = DataKey::PartyEscrow(party.clone(), position);
+        env.storage().persistent().get::<_, BytesN<32>>(&key)
+    }
+
+    /// Number of escrows currently in `status`.
+    pub fn get_status_escrow_count(env: &Env, status: &EscrowStatus) -> u32 {
+        let key = DataKey::StatusEscrowCount(status.clone());
+        env.storage()
+            .persistent()
+            .get::<_, u32>(&key)
+            .unwrap_or_default()
+    }
+
+    /// ID of the escrow at `position` in the index of `status`; positions stay dense
+    /// as escrows leave the status.
+    pub fn get_status_escrow(
+        env: &Env,
+        status: &EscrowStatus,
+        position: u32,
+    ) -> Option<BytesN<32>> {
+        let key = DataKey::StatusEscrow(status.clone(), position);
+        env.storage().persistent().get::<_, BytesN<32>>(&key)
+    }
+
+    fn add_to_party(env: &Env, party: &Address, escrow_id: &BytesN<32>) {
+        let count = Self::get_party_escrow_count(env, party);
+        let key = DataKey::PartyEscrow(party.clone(), count);
+        env.storage().persistent().set(&key, escrow_id);
+        let key = DataKey::PartyEscrowCount(party.clone());
+        env.storage().persistent().set(&key, &(count + 1));
+    }
+
+    fn add_to_status(env: &Env, status: &EscrowStatus, escrow_id: &BytesN<32>) {
+        let count = Self::get_status_escrow_count(env, status);
+        let key = DataKey::StatusEscrow(status.clone(), count);
+        env.storage().persistent().set(&key, escrow_id);
+        let key = DataKey::StatusPosition(escrow_id.clone());
+        env.storage().persistent().set(&key, &count);
+        let key = DataKey::StatusEscrowCount(status.clone());
+        env.storage().persistent().set(&key, &(count + 1));
+    }
+
+    /// Remove an escrow from the index of `status` by moving the last entry into its slot.
+    fn remove_from_status(env: &Env, status: &EscrowStatus, escrow_id: &BytesN<32>) {
+        let position_key = DataKey::StatusPosition(escrow_id.clone());
+        let Some(position) = env.storage().persistent().get::<_, u32>(&position_key) else {
+            return;
+        };
+        env.storage().persistent().remove(&position_key);
+
+        let last = Self::get_status_escrow_count(env, status) - 1;
+        if position != last {
+            if let Some(moved) = Self::get_status_escrow(env, status, last) {
+                let key = DataKey::StatusEscrow(status.clone(), position);
+                env.storage().persistent().set(&key, &moved);
+                let key = DataKey::StatusPosition(moved);
+                env.storage().persistent().set(&key, &position);
+            }
+        }
+        let key = DataKey::StatusEscrow(status.clone(), last);
+        env.storage().persistent().remove(&key);
+        let key = DataKey::StatusEscrowCount(status.clone());
+        env.storage().persistent().set(&key, &last);
+    }
+
+    /// Retrieve the dispute record of an escrow, if any.
+    pub fn get_dispute(env: &Env, escrow_id: &BytesN<32>) -> Option<Dispute> {
+        let key = DataKey::DisputeInfo(escrow_id.clone());
+        env.storage().persistent().get::<_, Dispute>(&key)
+    }
+
+    /// Save or update the dispute record of an escrow.
+    pub fn save_dispute(env: &Env, escrow_id: &BytesN<32>, dispute: &Dispute) {
+        let key = DataKey::DisputeInfo(escrow_id.clone());
+        env.storage().persistent().set(&key, dispute);
+    }
+
+    /// Remove dispute information once the dispute is resolved.
+    pub fn clear_dispute(env: &Env, escrow_id: &BytesN<32>) {
+        let key = DataKey::DisputeInfo(escrow_id.clone());
+        env.storage().persistent().remove(&key);
+    }
+
+    /// Get the appeal arbiter of an escrow, if rulings on it can be appealed.
+    pub fn get_appeal_arbiter(env: &Env, escrow_id: &BytesN<32>) -> Option<Address> {
+        let key = DataKey::AppealArbiter(escrow_id.clone());
+        env.storage().persistent().get::<_, Address>(&key)
+    }
+
+    /// Set the appeal arbiter of an escrow.
+    pub fn set_appeal_arbiter(env: &Env, escrow_id: &BytesN<32>, appeal_arbiter: &Address) {
+        let key = DataKey::AppealArbiter(escrow_id.clone());
+        env.storage().persistent().set(&key, appeal_arbiter);
+    }
+
+    /// Retrieve the provisional ruling on an escrow's dispute, if any.
+    pub fn get_ruling(env: &Env, escrow_id: &BytesN<32>) -> Option<ProvisionalRuling> {
+        let key = DataKey::Ruling(escrow_id.clone());
+        env.storage().persistent().get::<_, ProvisionalRuling>(&key)
+    }
+
+    /// Store the provisional ruling on an escrow's dispute.
+    pub fn save_ruling(env: &Env, escrow_id: &BytesN<32>, ruling: &ProvisionalRuling) {
+        let key = DataKey::Ruling(escrow_id.clone());
+        env.storage().persistent().set(&key, ruling);
+    }
+
+    /// Retrieve the appeal against an escrow's provisional ruling, if any.
+    pub fn get_appeal(env: &Env, escrow_id: &BytesN<32>) -> Option<Appeal> {
+        let key = DataKey::Appeal(escrow_id.clone());
+        env.storage().persistent().get::<_, Appeal>(&key)
+    }
+
+    /// Store the appeal against an escrow's provisional ruling.
+    pub fn save_appeal(env: &Env, escrow_id: &BytesN<32>, appeal: &Appeal) {
+        let key = DataKey::Appeal(escrow_id.clone());
+        env.storage().persistent().set(&key, appeal);
+    }
+
+    /// Remove the provisional ruling and any appeal against it.
+    pub fn clear_ruling(env: &Env, escrow_id: &BytesN<32>) {
+        let key = DataKey::Ruling(escrow_id.clone());
+        env.storage().persistent().remove(&key);
+        let key = DataKey::Appeal(escrow_id.clone());
+        env.storage().persistent().remove(&key);
+    }
+
+    /// Retrieve the open settlement offer on an escrow's dispute, if any.
+    pub fn get_offer(env: &Env, escrow_id: &BytesN<32>) -> Option<SettlementOffer> {
+        let key = DataKey::SettlementOffer(escrow_id.clone());
+        env.storage().persistent().get::<_, SettlementOffer>(&key)
+    }
+
+    /// Store the open settlement offer on an escrow's dispute.
+    pub fn save_offer(env: &Env, escrow_id: &BytesN<32>, offer: &SettlementOffer) {
+        let key = DataKey::SettlementOffer(escrow_id.clone());
+        env.storage().persistent().set(&key, offer);
+    }
+
+    /// Remove the settlement offer once it is accepted or the dispute ends.
+    pub fn clear_offer(env: &Env, escrow_id: &BytesN<32>) {
+        let key = DataKey::SettlementOffer(escrow_id.clone());
+        env.storage().persistent().remove(&key);
+    }
+
+    /// Get the arbiter registry settings, if configured.
+    pub fn get_registry_config(env: &Env) -> Option<ArbiterRegistryConfig> {
+        env.storage()
+            .instance()
+            .get::<_, ArbiterRegistryConfig>(&DataKey::RegistryConfig)
+    }
+
+    /// Store the arbiter registry settings.
+    pub fn save_registry_config(env: &Env, config: &ArbiterRegistryConfig) {
+        env.storage()
+            .instance()
+            .set(&DataKey::RegistryConfig, config);
+    }
+
+    /// Retrieve a registered arbiter's profile, if any.
+    pub fn get_arbiter(env: &Env, arbiter: &Address) -> Option<ArbiterProfile> {
+        let key = DataKey::Arbiter(arbiter.clone());
+        env.storage().persistent().get::<_, ArbiterProfile>(&key)
+    }
+
+    /// Save or update a registered arbiter's profile.
+    pub fn save_arbiter(env: &Env, arbiter: &Address, profile: &ArbiterProfile) {
+        let key = DataKey::Arbiter(arbiter.clone());
+        env.storage().persistent().set(&key, profile);
+    }
+
+    /// Remove an arbiter's profile once its bond has been withdrawn.
+    pub fn remove_arbiter(env: &Env, arbiter: &Address) {
+        let key = DataKey::Arbiter(arbiter.clone());
+        env.storage().persistent().remove(&key);
+    }
+
+    /// Retrieve an arbiter's ruling statistics; empty if it never ruled.
+    pub fn get_arbiter_stats(env: &Env, arbiter: &Address) -> ArbiterStats {
+        let key = DataKey::ArbiterStats(arbiter.clone());
+        env.storage()
+            .persistent()
+            .get::<_, ArbiterStats>(&key)
+            .unwrap_or_default()
+    }
+
+    /// Save an arbiter's ruling statistics.
+    pub fn save_arbiter_stats(env: &Env, arbiter: &Address, stats: &ArbiterStats) {
+        let key = DataKey::ArbiterStats(arbiter.clone());
+        env.storage().persistent().set(&key, stats);
+    }
+
+    /// Get the number of open disputes an arbiter decides.
+    pub fn get_arbiter_assignments(env: &Env, arbiter: &Address) -> u32 {
+        let key = DataKey::ArbiterAssignments(arbiter.clone());
+        env.storage().persistent().get::<_, u32>(&key).unwrap_or(0)
+    }
+
+    /// Store the number of open disputes an arbiter decides, dropping the entry at 0.
+    pub fn set_arbiter_assignments(env: &Env, arbiter: &Address, count: u32) {
+        let key = DataKey::ArbiterAssignments(arbiter.clone());
+        if count == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &count);
+        }
+    }
+
+    /// Get the arbiters eligible to sit on dispute panels.
+    pub fn get_arbiter_pool(env: &Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get::<_, Vec<Address>>(&DataKey::ArbiterPool)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Store the arbiters eligible to sit on dispute panels.
+    pub fn save_arbiter_pool(env: &Env, pool: &Vec<Address>) {
+        env.storage().persistent().set(&DataKey::ArbiterPool, pool);
+    }
+
+    /// Get the panel size requested for an escrow's disputes (0 for a single arbiter).
+    pub fn get_panel_size(env: &Env, escrow_id: &BytesN<32>) -> u32 {
+        let key = DataKey::PanelSize(escrow_id.clone());
+        env.storage()
+            .persistent()
+            .get::<_, u32>(&key)
+            .unwrap_or_default()
+    }
+
+    /// Set the panel size requested for an escrow's disputes.
+    pub fn set_panel_size(env: &Env, escrow_id: &BytesN<32>, size: u32) {
+        let key = DataKey::PanelSize(escrow_id.clone());
+        env.storage().persistent().set(&key, &size);
+    }
+
+    /// Retrieve the panel assigned to an escrow's dispute, if any.
+    pub fn get_panel(env: &Env, escrow_id: &BytesN<32>) -> Option<Panel> {
+        let key = DataKey::Panel(escrow_id.clone());
+        env.storage().persistent().get::<_, Panel>(&key)
+    }
+
+    /// Save or update the panel assigned to an escrow's dispute.
+    pub fn save_panel(env: &Env, escrow_id: &BytesN<32>, panel: &Panel) {
+        let key = DataKey::Panel(escrow_id.clone());
+        env.storage().persistent().set(&key, panel);
+    }
+
+    /// Remove the panel once its dispute is over.
+    pub fn clear_panel(env: &Env, escrow_id: &BytesN<32>) {
+        let key = DataKey::Panel(escrow_id.clone());
+        env.storage().persistent().remove(&key);
+    }
+
+    /// Retrieve the deduction claim filed against an escrow, if any.
+    pub fn get_claim(env: &Env, escrow_id: &BytesN<32>) -> Option<DeductionClaim> {
+        let key = DataKey::DeductionClaim(escrow_id.clone());
+        env.storage().persistent().get::<_, DeductionClaim>(&key)
+    }
+
+    /// Save or update the deduction claim for an escrow.
+    pub fn save_claim(env: &Env, escrow_id: &BytesN<32>, claim: &DeductionClaim) {
+        let key = DataKey::DeductionClaim(escrow_id.clone());
+        env.storage().persistent().set(&key, claim);
+    }
+
+    /// Retrieve the final payout record of a settled escrow.
+    pub fn get_settlement(env: &Env, escrow_id: &BytesN<32>) -> Option<Settlement> {
+        let key = DataKey::Settlement(escrow_id.clone());
+        env.storage().persistent().get::<_, Settlement>(&key)
+    }
+
+    /// Record the final payout of an escrow.
+    pub fn save_settlement(env: &Env, escrow_id: &BytesN<32>, settlement: &Settlement) {
+        let key = DataKey::Settlement(escrow_id.clone());
+        env.storage().persistent().set(&key, settlement);
+    }
+
+    /// Retrieve the early payout of a partly contested deduction claim, if any.
+    pub fn get_partial_release(env: &Env, escrow_id: &BytesN<32>) -> Option<Settlement> {
+        let key = DataKey::PartialRelease(escrow_id.clone());
+        env.storage().persistent().get::<_, Settlement>(&key)
+    }
+
+    /// Record the early payout of a partly contested deduction claim.
+    pub fn save_partial_release(env: &Env, escrow_id: &BytesN<32>, release: &Settlement) {
+        let key = DataKey::PartialRelease(escrow_id.clone());
+        env.storage().persistent().set(&key, release);
+    }
+
+    /// Retrieve the milestones of a milestone escrow, if it is one.
+    pub fn get_milestones(env: &Env, escrow_id: &BytesN<32>) -> Option<Vec<Milestone>> {
+        let key = DataKey::Milestones(escrow_id.clone());
+        env.storage().persistent().get::<_, Vec<Milestone>>(&key)
+    }
+
+    /// Store the milestones of a milestone escrow.
+    pub fn save_milestones(env: &Env, escrow_id: &BytesN<32>, milestones: &Vec<Milestone>) {
+        let key = DataKey::Milestones(escrow_id.clone());
+        env.storage().persistent().set(&key, milestones);
+    }
+
+    /// Get the number of milestones already settled.
+    pub fn get_milestones_settled(env: &Env, escrow_id: &BytesN<32>) -> u32 {
+        let key = DataKey::MilestonesSettled(escrow_id.clone());
+        env.storage()
+            .persistent()
+            .get::<_, u32>(&key)
+            .unwrap_or_default()
+    }
+
+    /// Record the payout of milestone `index` and advance to the next one.
+    pub fn save_milestone_settlement(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        index: u32,
+        settlement: &Settlement,
+    ) {
+        let key = DataKey::MilestoneSettlement(escrow_id.clone(), index);
+        env.storage().persistent().set(&key, settlement);
+        let key = DataKey::MilestonesSettled(escrow_id.clone());
+        env.storage().persistent().set(&key, &(index + 1));
+    }
+
+    /// Retrieve the payout of milestone `index`, if settled.
+    pub fn get_milestone_settlement(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        index: u32,
+    ) -> Option<Settlement> {
+        let key = DataKey::MilestoneSettlement(escrow_id.clone(), index);
+        env.storage().persistent().get::<_, Settlement>(&key)
+    }
+
+    /// Retrieve the installment schedule of an escrow, if it has one.
+    pub fn get_installments(env: &Env, escrow_id: &BytesN<32>) -> Option<Vec<Installment>> {
+        let key = DataKey::Installments(escrow_id.clone());
+        env.storage().persistent().get::<_, Vec<Installment>>(&key)
+    }
+
+    /// Store the installment schedule of an escrow.
+    pub fn save_installments(env: &Env, escrow_id: &BytesN<32>, installments: &Vec<Installment>) {
+        let key = DataKey::Installments(escrow_id.clone());
+        env.storage().persistent().set(&key, installments);
+    }
+
+    /// Retrieve the open adjustment request on an escrow, if any.
+    pub fn get_adjustment(env: &Env, escrow_id: &BytesN<32>) -> Option<AdjustmentRequest> {
+        let key = DataKey::Adjustment(escrow_id.clone());
+        env.storage().persistent().get::<_, AdjustmentRequest>(&key)
+    }
+
+    /// Store the open adjustment request on an escrow.
+    pub fn save_adjustment(env: &Env, escrow_id: &BytesN<32>, request: &AdjustmentRequest) {
+        let key = DataKey::Adjustment(escrow_id.clone());
+        env.storage().persistent().set(&key, request);
+    }
+
+    /// Remove the adjustment request once approved or rejected.
+    pub fn clear_adjustment(env: &Env, escrow_id: &BytesN<32>) {
+        let key = DataKey::Adjustment(escrow_id.clone());
+        env.storage().persistent().remove(&key);
+    }
+
+    /// Retrieve the yield policy of an escrow, if it places funds in a vault.
+    pub fn get_yield_policy(env: &Env, escrow_id: &BytesN<32>) -> Option<YieldPolicy> {
+        let key = DataKey::YieldPolicy(escrow_id.clone());
+        env.storage().persistent().get::<_, YieldPolicy>(&key)
+    }
+
+    /// Store the yield policy of an escrow.
+    pub fn save_yield_policy(env: &Env, escrow_id: &BytesN<32>, policy: &YieldPolicy) {
+        let key = DataKey::YieldPolicy(escrow_id.clone());
+        env.storage().persistent().set(&key, policy);
+    }
+
+    /// Retrieve the funds an escrow has in its yield vault, if any.
+    pub fn get_vault_position(env: &Env, escrow_id: &BytesN<32>) -> Option<VaultPosition> {
+        let key = DataKey::VaultPosition(escrow_id.clone());
+        env.storage().persistent().get::<_, VaultPosition>(&key)
+    }
+
+    /// Store the funds an escrow has in its yield vault.
+    pub fn save_vault_position(env: &Env, escrow_id: &BytesN<32>, position: &VaultPosition) {
+        let key = DataKey::VaultPosition(escrow_id.clone());
+        env.storage().persistent().set(&key, position);
+    }
+
+    /// Remove the vault position once the funds are withdrawn.
+    pub fn clear_vault_position(env: &Env, escrow_id: &BytesN<32>) {
+        let key = DataKey::VaultPosition(escrow_id.clone());
+        env.storage().persistent().remove(&key);
+    }
+
+    /// Retrieve the principal an escrow lost in its yield vault (0 if none).
+    pub fn get_vault_loss(env: &Env, escrow_id: &BytesN<32>) -> i128 {
+        let key = DataKey::VaultLoss(escrow_id.clone());
+        env.storage()
+            .persistent()
+            .get::<_, i128>(&key)
+            .unwrap_or_default()
+    }
+
+    /// Record the principal an escrow lost in its yield vault.
+    pub fn save_vault_loss(env: &Env, escrow_id: &BytesN<32>, loss: i128) {
+        let key = DataKey::VaultLoss(escrow_id.clone());
+        env.storage().persistent().set(&key, &loss);
+    }
+
+    /// Get the escrow ID linked to a rent agreement, if any.
+    pub fn get_agreement_escrow(env: &Env, agreement_id: &String) -> Option<BytesN<32>> {
+        let key = DataKey::AgreementEscrow(agreement_id.clone());
+        env.storage().persistent().get::<_, BytesN<32>>(&key)
+    }
+
+    /// Link a rent agreement to its escrow ID.
+    pub fn set_agreement_escrow(env: &Env, agreement_id: &String, escrow_id: &BytesN<32>) {
+        let key = DataKey::AgreementEscrow(agreement_id.clone());
+        env.storage().persistent().set(&key, escrow_id);
+    }
+
+    /// Retrieve the signer set of an escrow.
+    /// Falls back to depositor, beneficiary and arbiter at weight 1 with a threshold of 2.
+    pub fn get_signers(env: &Env, escrow: &Escrow) -> SignerSet {
+        let key = DataKey::Signers(escrow.id.clone());
+        match env.storage().persistent().get::<_, SignerSet>(&key) {
+            Some(signer_set) => signer_set,
+            None => {
+                let mut signers = Vec::new(env);
+                for address in [&escrow.depositor, &escrow.beneficiary, &escrow.arbiter] {
+                    signers.push_back(Signer {
+                        address: address.clone(),
+                        weight: 1,
+                    });
+                }
+                SignerSet {
+                    signers,
+                    threshold: 2,
+                }
+            }
+        }
+    }
+
+    /// Store an explicit signer set for an escrow.
+    pub fn save_signers(env: &Env, escrow_id: &BytesN<32>, signer_set: &SignerSet) {
+        let key = DataKey::Signers(escrow_id.clone());
+        env.storage().persistent().set(&key, signer_set);
     }
 
     /// Retrieve all approvals for an escrow release.
@@ -36,13 +526,42 @@ impl EscrowStorage {
         }
     }
 
-    /// Add a new approval for fund release.
-    /// Appends to existing approvals list.
-    pub fn add_approval(env: &Env, escrow_id: &BytesN<32>, approval: ReleaseApproval) {
-        let mut approvals = Self::get_approvals(env, escrow_id);
-        approvals.push_back(approval);
+    /// Replace the stored approvals for an escrow.
+    pub fn save_approvals(env: &Env, escrow_id: &BytesN<32>, approvals: &Vec<ReleaseApproval>) {
         let key = DataKey::Approvals(escrow_id.clone());
-        env.storage().persistent().set(&key, &approvals);
+        env.storage().persistent().set(&key, approvals);
+    }
+
+    /// Get how long approvals on an escrow stay live, in seconds.
+    /// Returns 0 (approvals never expire) if no TTL was set.
+    pub fn get_approval_ttl(env: &Env, escrow_id: &BytesN<32>) -> u64 {
+        let key = DataKey::ApprovalTtl(escrow_id.clone());
+        env.storage()
+            .persistent()
+            .get::<_, u64>(&key)
+            .unwrap_or_default()
+    }
+
+    /// Set how long approvals on an escrow stay live, in seconds.
+    pub fn set_approval_ttl(env: &Env, escrow_id: &BytesN<32>, ttl: u64) {
+        let key = DataKey::ApprovalTtl(escrow_id.clone());
+        env.storage().persistent().set(&key, &ttl);
+    }
+
+    /// Get the time after which an uncontested escrow can be refunded.
+    /// Returns 0 if none was set.
+    pub fn get_release_after(env: &Env, escrow_id: &BytesN<32>) -> u64 {
+        let key = DataKey::ReleaseAfter(escrow_id.clone());
+        env.storage()
+            .persistent()
+            .get::<_, u64>(&key)
+            .unwrap_or_default()
+    }
+
+    /// Set the time after which an uncontested escrow can be refunded.
+    pub fn set_release_after(env: &Env, escrow_id: &BytesN<32>, release_after: u64) {
+        let key = DataKey::ReleaseAfter(escrow_id.clone());
+        env.storage().persistent().set(&key, &release_after);
     }
 
     /// Clear all approvals for an escrow.
@@ -67,4 +586,54 @@ impl EscrowStorage {
             .instance()
             .set(&DataKey::EscrowCount, &(count + 1));
     }
+
+    /// Get the total amount of `token` held across funded escrows.
+    pub fn get_total_held(env: &Env, token: &Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get::<_, i128>(&DataKey::TotalHeld(token.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Record `amount` of `token` entering escrow custody.
+    pub fn add_total_held(env: &Env, token: &Address, amount: i128) {
+        let total = Self::get_total_held(env, token);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalHeld(token.clone()), &(total + amount));
+    }
+
+    /// Record `amount` of `token` leaving escrow custody.
+    pub fn sub_total_held(env: &Env, token: &Address, amount: i128) {
+        let total = Self::get_total_held(env, token);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalHeld(token.clone()), &(total - amount));
+    }
+
+    /// Retrieve the contract admin and configuration, if initialized.
+    pub fn get_state(env: &Env) -> Option<ContractState> {
+        env.storage()
+            .instance()
+            .get::<_, ContractState>(&DataKey::State)
+    }
+
+    /// Store the contract admin and configuration.
+    pub fn save_state(env: &Env, state: &ContractState) {
+        env.storage().instance().set(&DataKey::State, state);
+        env.storage().instance().extend_ttl(500000, 500000);
+    }
+
+    /// Get the active configuration, falling back to defaults before initialization.
+    pub fn get_config(env: &Env) -> EscrowConfig {
+        match Self::get_state(env) {
+            Some(state) => state.config,
+            None => EscrowConfig {
+                dispute_response_window: DEFAULT_DISPUTE_RESPONSE_WINDOW,
+                dispute_ruling_window: DEFAULT_DISPUTE_RULING_WINDOW,
+                appeal_window: DEFAULT_APPEAL_WINDOW,
+                appeal_bond_bps: DEFAULT_APPEAL_BOND_BPS,
+            },
+        }
+    }
 }
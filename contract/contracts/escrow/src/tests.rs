@@ -1,10 +1,122 @@
 //! Tests for the Escrow contract.
 
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, BytesN, Env};
+use soroban_sdk::testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke};
+use soroban_sdk::token::{StellarAssetClient as TokenAdminClient, TokenClient};
+use soroban_sdk::{vec, Address, BytesN, Env, Event, IntoVal, String, Val, Vec};
 
 use crate::access::AccessControl;
-use crate::types::{Escrow, EscrowStatus};
+use crate::errors::EscrowError;
+use crate::registry::UNBONDING_PERIOD;
+use crate::storage::{
+    DEFAULT_APPEAL_BOND_BPS, DEFAULT_APPEAL_WINDOW, DEFAULT_DISPUTE_RESPONSE_WINDOW,
+    DEFAULT_DISPUTE_RULING_WINDOW,
+};
+use crate::types::{
+    ApprovalProgress, ArbiterRegistryConfig, ClaimStatus, DeductionCategory, DeductionItem,
+    DisputeCategory, DisputeReason, Escrow, EscrowConfig, EscrowStatus, Installment, Milestone,
+    ReleaseSplit, ResolutionPath, Signer, VaultPosition, YieldPolicy,
+};
+use crate::{
+    ApprovalAdded, ApprovalRevoked, DisputeOpened, DisputeResolved, DisputeWithdrawn,
+    EscrowContract, EscrowContractClient, EscrowCreated, EscrowFunded, EscrowSettled,
+    EvidenceAdded, PartialRelease,
+};
+
+struct Setup<'a> {
+    client: EscrowContractClient<'a>,
+    admin: Address,
+    depositor: Address,
+    beneficiary: Address,
+    arbiter: Address,
+    token: Address,
+    bond_token: Address,
+}
+
+const INITIAL_BALANCE: i128 = 10_000;
+const ARBITER_BOND: i128 = 500;
+
+fn default_config() -> EscrowConfig {
+    EscrowConfig {
+        dispute_response_window: DEFAULT_DISPUTE_RESPONSE_WINDOW,
+        dispute_ruling_window: DEFAULT_DISPUTE_RULING_WINDOW,
+        appeal_window: DEFAULT_APPEAL_WINDOW,
+        appeal_bond_bps: DEFAULT_APPEAL_BOND_BPS,
+    }
+}
+
+fn registry_config(s: &Setup, fee_bps: u32) -> ArbiterRegistryConfig {
+    ArbiterRegistryConfig {
+        bond_token: s.bond_token.clone(),
+        min_bond: ARBITER_BOND,
+        fee_bps,
+    }
+}
+
+/// Register a new contract and deposit token, with the arbiter bonded in a separate token.
+fn setup(env: &Env) -> Setup<'_> {
+    let contract_id = env.register(EscrowContract, ());
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    let bond_token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+
+    env.mock_all_auths();
+    let s = Setup {
+        client: EscrowContractClient::new(env, &contract_id),
+        admin: Address::generate(env),
+        depositor: Address::generate(env),
+        beneficiary: Address::generate(env),
+        arbiter: Address::generate(env),
+        token,
+        bond_token,
+    };
+    TokenAdminClient::new(env, &s.token).mint(&s.depositor, &INITIAL_BALANCE);
+
+    s.client.initialize(&s.admin, &default_config());
+    s.client.configure_arbiter_registry(&registry_config(&s, 0));
+    TokenAdminClient::new(env, &s.bond_token).mint(&s.arbiter, &ARBITER_BOND);
+    s.client.register_arbiter(&s.arbiter, &ARBITER_BOND);
+
+    s
+}
+
+fn split(beneficiary_amount: i128, depositor_amount: i128, arbiter_fee: i128) -> ReleaseSplit {
+    ReleaseSplit {
+        beneficiary_amount,
+        depositor_amount,
+        arbiter_fee,
+    }
+}
+
+fn progress(weight: u32, threshold: u32) -> ApprovalProgress {
+    ApprovalProgress { weight, threshold }
+}
+
+fn balance(env: &Env, token: &Address, owner: &Address) -> i128 {
+    TokenClient::new(env, token).balance(owner)
+}
+
+fn assert_event_published(env: &Env, contract: &Address, event: &impl Event) {
+    let published = (contract.clone(), event.topics(env), event.data(env));
+    assert!(env.events().all().contains(published));
+}
+
+fn advance_time(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+fn create_escrow(env: &Env, s: &Setup) -> BytesN<32> {
+    s.client.create(
+        &String::from_str(env, "AGREEMENT_001"),
+        &s.depositor,
+        &s.beneficiary,
+        &s.arbiter,
+        &1000,
+        &s.token,
+    )
+}
 
 fn create_test_escrow(env: &Env) -> (Escrow, Address, Address, Address) {
     let depositor = Address::generate(env);
@@ -14,14 +126,15 @@ fn create_test_escrow(env: &Env) -> (Escrow, Address, Address, Address) {
 
     let escrow = Escrow {
         id: BytesN::<32>::from_array(env, &[1u8; 32]),
+        agreement_id: String::from_str(env, "AGREEMENT_001"),
         depositor: depositor.clone(),
         beneficiary: beneficiary.clone(),
         arbiter: arbiter.clone(),
         amount: 1000,
+        funded: 1000,
         token,
         status: EscrowStatus::Funded,
         created_at: 0,
-        dispute_reason: None,
     };
 
     (escrow, depositor, beneficiary, arbiter)
@@ -89,3 +202,3037 @@ fn test_is_primary_party() {
     let other = Address::generate(&env);
     assert!(AccessControl::is_primary_party(&escrow, &other).is_err());
 }
+
+#[test]
+fn test_create_escrow_via_client() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    let escrow = s.client.get_escrow(&escrow_id);
+
+    assert_eq!(escrow.depositor, s.depositor);
+    assert_eq!(escrow.beneficiary, s.beneficiary);
+    assert_eq!(escrow.arbiter, s.arbiter);
+    assert_eq!(escrow.amount, 1000);
+    assert_eq!(escrow.status, EscrowStatus::Pending);
+    assert_eq!(s.client.get_escrow_count(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_create_escrow_requires_beneficiary_auth() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.set_auths(&[]);
+
+    create_escrow(&env, &s);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_create_escrow_invalid_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    s.client.create(
+        &String::from_str(&env, "AGREEMENT_001"),
+        &s.depositor,
+        &s.beneficiary,
+        &s.arbiter,
+        &0,
+        &s.token,
+    );
+}
+
+#[test]
+fn test_fund_and_release_via_client() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(1000, 0, 0));
+    assert_eq!(
+        s.client.get_approval_count(&escrow_id, &split(1000, 0, 0)),
+        progress(1, 2)
+    );
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(1000, 0, 0));
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 1000);
+    assert_eq!(balance(&env, &s.token, &s.client.address), 0);
+    assert_eq!(s.client.get_total_held(&s.token), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_fund_escrow_not_depositor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.beneficiary);
+}
+
+#[test]
+#[should_panic]
+fn test_fund_escrow_requires_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    env.set_auths(&[]);
+
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_approve_release_outsider() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    let outsider = Address::generate(&env);
+    s.client
+        .approve_release(&escrow_id, &outsider, &split(1000, 0, 0));
+}
+
+#[test]
+fn test_dispute_and_resolve_via_client() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    let reason = String::from_str(&env, "Damage not documented at move-in");
+    s.client
+        .initiate_dispute(&escrow_id, &s.depositor, &DisputeCategory::Damage, &reason);
+    assert!(s.client.is_disputed(&escrow_id));
+    let dispute = s.client.get_dispute_info(&escrow_id).unwrap();
+    assert_eq!(dispute.reason, DisputeReason::Statement(reason));
+    assert_eq!(dispute.opened_by, s.depositor);
+    assert_eq!(dispute.category, DisputeCategory::Damage);
+    assert!(dispute.evidence.is_empty());
+
+    advance_time(&env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(0, 1000, 0));
+    assert!(!s.client.is_disputed(&escrow_id));
+    assert_eq!(s.client.get_dispute_info(&escrow_id), None);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE);
+    assert_eq!(balance(&env, &s.token, &s.client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_resolve_dispute_non_arbiter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    s.client.initiate_dispute(
+        &escrow_id,
+        &s.beneficiary,
+        &DisputeCategory::Other,
+        &String::from_str(&env, "Unpaid utilities"),
+    );
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.beneficiary, &split(1000, 0, 0));
+}
+
+#[test]
+fn test_fund_escrow_takes_custody() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    assert_eq!(balance(&env, &s.token, &s.client.address), 0);
+
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    assert_eq!(
+        balance(&env, &s.token, &s.depositor),
+        INITIAL_BALANCE - 1000
+    );
+    assert_eq!(balance(&env, &s.token, &s.client.address), 1000);
+    assert_eq!(s.client.get_total_held(&s.token), 1000);
+}
+
+#[test]
+#[should_panic]
+fn test_fund_escrow_insufficient_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = s.client.create(
+        &String::from_str(&env, "AGREEMENT_001"),
+        &s.depositor,
+        &s.beneficiary,
+        &s.arbiter,
+        &(INITIAL_BALANCE + 1),
+        &s.token,
+    );
+
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_release_fails_when_balance_below_total_held() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    // Simulate an accounting drift where the contract owes more than it holds
+    env.as_contract(&s.client.address, || {
+        crate::EscrowStorage::add_total_held(&env, &s.token, 1);
+    });
+
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(1000, 0, 0));
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(1000, 0, 0));
+}
+
+#[test]
+fn test_escrow_ids_are_unique_and_deterministic() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let first_id = create_escrow(&env, &s);
+    let second_agreement = String::from_str(&env, "AGREEMENT_002");
+    let expected_id = env.as_contract(&s.client.address, || {
+        crate::EscrowHandler::derive_id(&env, &second_agreement, &s.depositor)
+    });
+    let second_id = s.client.create(
+        &second_agreement,
+        &s.depositor,
+        &s.beneficiary,
+        &s.arbiter,
+        &500,
+        &s.token,
+    );
+
+    assert_ne!(first_id, second_id);
+    assert_eq!(second_id, expected_id);
+    assert_eq!(s.client.get_escrow(&first_id).amount, 1000);
+    assert_eq!(s.client.get_escrow(&second_id).amount, 500);
+    assert_eq!(s.client.get_escrow_count(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_create_duplicate_escrow_for_agreement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    create_escrow(&env, &s);
+    create_escrow(&env, &s);
+}
+
+#[test]
+fn test_get_escrow_by_agreement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    let escrow = s
+        .client
+        .get_escrow_by_agreement(&String::from_str(&env, "AGREEMENT_001"));
+    assert_eq!(escrow.id, escrow_id);
+
+    let missing = s
+        .client
+        .try_get_escrow_by_agreement(&String::from_str(&env, "AGREEMENT_404"));
+    assert_eq!(missing, Err(Ok(crate::EscrowError::EscrowNotFound)));
+}
+
+#[test]
+fn test_approved_refund_marks_refunded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(0, 1000, 0));
+    s.client
+        .approve_release(&escrow_id, &s.arbiter, &split(0, 1000, 0));
+
+    assert_event_published(
+        &env,
+        &s.client.address,
+        &EscrowSettled {
+            escrow_id: escrow_id.clone(),
+            beneficiary: s.beneficiary.clone(),
+            beneficiary_amount: 0,
+            depositor: s.depositor.clone(),
+            depositor_amount: 1000,
+            arbiter: s.arbiter.clone(),
+            arbiter_fee: 0,
+            path: ResolutionPath::Multisig,
+            status: EscrowStatus::Refunded,
+        },
+    );
+
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+
+    let settlement = s.client.get_settlement(&escrow_id).unwrap();
+    assert_eq!(settlement.split, split(0, 1000, 0));
+    assert_eq!(settlement.path, ResolutionPath::Multisig);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE);
+}
+
+#[test]
+fn test_arbiter_release_records_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    s.client.initiate_dispute(
+        &escrow_id,
+        &s.beneficiary,
+        &DisputeCategory::Other,
+        &String::from_str(&env, "Broken window"),
+    );
+
+    advance_time(&env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(1000, 0, 0));
+
+    assert_event_published(
+        &env,
+        &s.client.address,
+        &EscrowSettled {
+            escrow_id: escrow_id.clone(),
+            beneficiary: s.beneficiary.clone(),
+            beneficiary_amount: 1000,
+            depositor: s.depositor.clone(),
+            depositor_amount: 0,
+            arbiter: s.arbiter.clone(),
+            arbiter_fee: 0,
+            path: ResolutionPath::Arbiter,
+            status: EscrowStatus::Released,
+        },
+    );
+
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+
+    let settlement = s.client.get_settlement(&escrow_id).unwrap();
+    assert_eq!(settlement.split, split(1000, 0, 0));
+    assert_eq!(settlement.path, ResolutionPath::Arbiter);
+}
+
+#[test]
+fn test_arbiter_refund_marks_refunded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    s.client.initiate_dispute(
+        &escrow_id,
+        &s.depositor,
+        &DisputeCategory::Other,
+        &String::from_str(&env, "Landlord unresponsive"),
+    );
+
+    advance_time(&env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(0, 1000, 0));
+
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().path,
+        ResolutionPath::Arbiter
+    );
+}
+
+#[test]
+fn test_partial_release_by_matching_splits() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    let damages = split(300, 700, 0);
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &damages);
+    s.client.approve_release(&escrow_id, &s.depositor, &damages);
+
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 300);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 300);
+    assert_eq!(balance(&env, &s.token, &s.client.address), 0);
+}
+
+#[test]
+fn test_differing_splits_do_not_combine() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(400, 600, 0));
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(100, 900, 0));
+
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+    assert_eq!(
+        s.client.get_approval_count(&escrow_id, &split(400, 600, 0)),
+        progress(1, 2)
+    );
+    assert_eq!(
+        s.client.get_approval_count(&escrow_id, &split(100, 900, 0)),
+        progress(1, 2)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_split_must_match_escrow_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(500, 400, 0));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_split_rejects_negative_amounts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(1200, -200, 0));
+}
+
+#[test]
+fn test_arbiter_ruling_with_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    s.client
+        .configure_arbiter_registry(&registry_config(&s, 500));
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    s.client.initiate_dispute(
+        &escrow_id,
+        &s.depositor,
+        &DisputeCategory::Other,
+        &String::from_str(&env, "Carpet cleaning overcharged"),
+    );
+
+    advance_time(&env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(250, 700, 50));
+
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 250);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 300);
+    assert_eq!(balance(&env, &s.token, &s.arbiter), 50);
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().split,
+        split(250, 700, 50)
+    );
+}
+
+fn deduction(env: &Env, category: DeductionCategory, amount: i128) -> DeductionItem {
+    DeductionItem {
+        category,
+        amount,
+        evidence_hash: BytesN::from_array(env, &[7u8; 32]),
+    }
+}
+
+fn file_move_out_claim(env: &Env, s: &Setup, escrow_id: &BytesN<32>) {
+    let items = vec![
+        env,
+        deduction(env, DeductionCategory::Cleaning, 150),
+        deduction(env, DeductionCategory::Damage, 400),
+    ];
+    s.client
+        .file_deduction_claim(escrow_id, &s.beneficiary, &items);
+}
+
+#[test]
+fn test_accept_full_deduction_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    file_move_out_claim(&env, &s, &escrow_id);
+
+    s.client
+        .respond_to_claim(&escrow_id, &s.depositor, &vec![&env, 0, 1]);
+
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().path,
+        ResolutionPath::DeductionClaim
+    );
+    assert_eq!(
+        s.client.get_deduction_claim(&escrow_id).unwrap().status,
+        ClaimStatus::Accepted
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 550);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 550);
+}
+
+#[test]
+fn test_partially_contested_claim_releases_uncontested_part() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    file_move_out_claim(&env, &s, &escrow_id);
+
+    // Tenant accepts cleaning, contests the damage line
+    s.client
+        .respond_to_claim(&escrow_id, &s.depositor, &vec![&env, 0]);
+
+    // The early payout is published alongside the dispute and recorded
+    assert_event_published(
+        &env,
+        &s.client.address,
+        &PartialRelease {
+            escrow_id: escrow_id.clone(),
+            beneficiary: s.beneficiary.clone(),
+            beneficiary_amount: 150,
+            depositor: s.depositor.clone(),
+            depositor_amount: 450,
+            held: 400,
+            path: ResolutionPath::DeductionClaim,
+            status: EscrowStatus::Disputed,
+        },
+    );
+    let release = s.client.get_partial_release(&escrow_id).unwrap();
+    assert_eq!(release.split, split(150, 450, 0));
+    assert_eq!(release.path, ResolutionPath::DeductionClaim);
+
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Disputed);
+    assert_eq!(escrow.amount, 400);
+    let dispute = s.client.get_dispute_info(&escrow_id).unwrap();
+    assert_eq!(
+        dispute.reason,
+        DisputeReason::ContestedDeductions(vec![&env, 1])
+    );
+    assert_eq!(dispute.opened_by, s.depositor);
+    assert_eq!(dispute.category, DisputeCategory::ContestedDeductions);
+    assert_eq!(
+        s.client.get_deduction_claim(&escrow_id).unwrap().status,
+        ClaimStatus::PartiallyAccepted
+    );
+
+    // Cleaning to landlord, unclaimed 450 back to tenant, 400 still held
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 150);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 550);
+    assert_eq!(balance(&env, &s.token, &s.client.address), 400);
+    assert_eq!(s.client.get_total_held(&s.token), 400);
+
+    advance_time(&env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(200, 200, 0));
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 350);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 350);
+    assert_eq!(balance(&env, &s.token, &s.client.address), 0);
+}
+
+#[test]
+fn test_fully_contested_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    file_move_out_claim(&env, &s, &escrow_id);
+
+    s.client
+        .respond_to_claim(&escrow_id, &s.depositor, &Vec::new(&env));
+
+    assert_eq!(s.client.get_escrow(&escrow_id).amount, 550);
+    assert_eq!(
+        s.client.get_deduction_claim(&escrow_id).unwrap().status,
+        ClaimStatus::Contested
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 0);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 550);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_claim_cannot_exceed_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    let items = vec![
+        &env,
+        deduction(&env, DeductionCategory::UnpaidRent, 800),
+        deduction(&env, DeductionCategory::Damage, 400),
+    ];
+    s.client
+        .file_deduction_claim(&escrow_id, &s.beneficiary, &items);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_only_beneficiary_files_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    let items = vec![&env, deduction(&env, DeductionCategory::Other, 100)];
+    s.client
+        .file_deduction_claim(&escrow_id, &s.depositor, &items);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_respond_with_duplicate_lines() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    file_move_out_claim(&env, &s, &escrow_id);
+
+    s.client
+        .respond_to_claim(&escrow_id, &s.depositor, &vec![&env, 0, 0]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_respond_after_window_closed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    file_move_out_claim(&env, &s, &escrow_id);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = crate::deductions::CLAIM_RESPONSE_WINDOW + 1);
+    s.client
+        .respond_to_claim(&escrow_id, &s.depositor, &Vec::new(&env));
+}
+
+#[test]
+fn test_unanswered_claim_settles_after_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    file_move_out_claim(&env, &s, &escrow_id);
+
+    assert_eq!(
+        s.client.try_settle_unanswered_claim(&escrow_id),
+        Err(Ok(crate::EscrowError::ClaimWindowOpen))
+    );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = crate::deductions::CLAIM_RESPONSE_WINDOW + 1);
+    s.client.settle_unanswered_claim(&escrow_id);
+
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 550);
+}
+
+fn open_statement_dispute(env: &Env, s: &Setup, escrow_id: &BytesN<32>) {
+    s.client.initiate_dispute(
+        escrow_id,
+        &s.depositor,
+        &DisputeCategory::Damage,
+        &String::from_str(env, "Pre-existing damage charged to tenant"),
+    );
+}
+
+fn evidence_hash(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+#[test]
+fn test_dispute_records_deadlines_and_evidence() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(&env, &s, &escrow_id);
+
+    let photos = String::from_str(&env, "ipfs://move-in-photos");
+    s.client
+        .submit_evidence(&escrow_id, &s.depositor, &evidence_hash(&env, 1), &photos);
+    advance_time(&env, 60);
+    let invoice = String::from_str(&env, "ipfs://repair-invoice");
+    s.client.submit_evidence(
+        &escrow_id,
+        &s.beneficiary,
+        &evidence_hash(&env, 2),
+        &invoice,
+    );
+
+    let dispute = s.client.get_dispute_info(&escrow_id).unwrap();
+    assert_eq!(dispute.opened_at, 1_000);
+    assert_eq!(
+        dispute.response_deadline,
+        1_000 + DEFAULT_DISPUTE_RESPONSE_WINDOW
+    );
+    assert_eq!(
+        dispute.ruling_deadline,
+        1_000 + DEFAULT_DISPUTE_RESPONSE_WINDOW + DEFAULT_DISPUTE_RULING_WINDOW
+    );
+    assert_eq!(dispute.evidence.len(), 2);
+
+    let first = dispute.evidence.get(0).unwrap();
+    assert_eq!(first.submitter, s.depositor);
+    assert_eq!(first.content_hash, evidence_hash(&env, 1));
+    assert_eq!(first.uri, photos);
+    assert_eq!(first.submitted_at, 1_000);
+
+    let second = dispute.evidence.get(1).unwrap();
+    assert_eq!(second.submitter, s.beneficiary);
+    assert_eq!(second.submitted_at, 1_060);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_arbiter_cannot_submit_evidence() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(&env, &s, &escrow_id);
+
+    s.client.submit_evidence(
+        &escrow_id,
+        &s.arbiter,
+        &evidence_hash(&env, 3),
+        &String::from_str(&env, "ipfs://notes"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_arbiter_cannot_rule_during_response_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(&env, &s, &escrow_id);
+
+    // Evidence from the opening party does not shorten the window
+    s.client.submit_evidence(
+        &escrow_id,
+        &s.depositor,
+        &evidence_hash(&env, 1),
+        &String::from_str(&env, "ipfs://move-in-photos"),
+    );
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(0, 1000, 0));
+}
+
+#[test]
+fn test_arbiter_rules_once_counter_party_responds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(&env, &s, &escrow_id);
+
+    s.client.submit_evidence(
+        &escrow_id,
+        &s.beneficiary,
+        &evidence_hash(&env, 2),
+        &String::from_str(&env, "ipfs://repair-invoice"),
+    );
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(300, 700, 0));
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_escalate_before_ruling_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(&env, &s, &escrow_id);
+
+    advance_time(&env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+    s.client.escalate_dispute(&escrow_id, &s.depositor);
+}
+
+#[test]
+fn test_escalated_dispute_resolved_by_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let admin = s.admin.clone();
+    s.client.update_config(&EscrowConfig {
+        dispute_response_window: 100,
+        dispute_ruling_window: 200,
+        appeal_window: 300,
+        appeal_bond_bps: 1_000,
+    });
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(&env, &s, &escrow_id);
+
+    advance_time(&env, 301);
+    s.client.escalate_dispute(&escrow_id, &s.beneficiary);
+    assert!(s.client.get_dispute_info(&escrow_id).unwrap().escalated);
+
+    // The arbiter missed its deadline and can no longer rule
+    assert!(s
+        .client
+        .try_resolve_dispute(&escrow_id, &s.arbiter, &split(0, 1000, 0))
+        .is_err());
+
+    s.client
+        .resolve_dispute(&escrow_id, &admin, &split(400, 600, 0));
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().path,
+        ResolutionPath::Admin
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 400);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_initialize_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    s.client
+        .initialize(&Address::generate(&env), &default_config());
+}
+
+fn signer(address: &Address, weight: u32) -> Signer {
+    Signer {
+        address: address.clone(),
+        weight,
+    }
+}
+
+#[test]
+fn test_default_signer_set_is_two_of_three() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    let signer_set = s.client.get_signers(&escrow_id);
+    assert_eq!(signer_set.threshold, 2);
+    assert_eq!(
+        signer_set.signers,
+        vec![
+            &env,
+            signer(&s.depositor, 1),
+            signer(&s.beneficiary, 1),
+            signer(&s.arbiter, 1)
+        ]
+    );
+}
+
+#[test]
+fn test_co_landlords_weighted_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let co_owner = Address::generate(&env);
+
+    // Two landlords, tenant and arbiter; tenant carries double weight, threshold 3
+    let escrow_id = create_escrow(&env, &s);
+    let signers = vec![
+        &env,
+        signer(&s.beneficiary, 1),
+        signer(&co_owner, 1),
+        signer(&s.depositor, 2),
+        signer(&s.arbiter, 1),
+    ];
+    s.client
+        .set_signers(&escrow_id, &s.beneficiary, &signers, &3);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    let refund = split(0, 1000, 0);
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &refund);
+    s.client.approve_release(&escrow_id, &co_owner, &refund);
+    assert_eq!(
+        s.client.get_approval_count(&escrow_id, &refund),
+        progress(2, 3)
+    );
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+
+    s.client.approve_release(&escrow_id, &s.depositor, &refund);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE);
+}
+
+#[test]
+fn test_three_of_five_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let extra_one = Address::generate(&env);
+    let extra_two = Address::generate(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    let signers = vec![
+        &env,
+        signer(&s.depositor, 1),
+        signer(&s.beneficiary, 1),
+        signer(&s.arbiter, 1),
+        signer(&extra_one, 1),
+        signer(&extra_two, 1),
+    ];
+    s.client
+        .set_signers(&escrow_id, &s.beneficiary, &signers, &3);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    let release = split(1000, 0, 0);
+    s.client.approve_release(&escrow_id, &extra_one, &release);
+    s.client.approve_release(&escrow_id, &extra_two, &release);
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &release);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_default_party_outside_custom_signer_set() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    let signers = vec![&env, signer(&s.depositor, 1), signer(&s.beneficiary, 1)];
+    s.client
+        .set_signers(&escrow_id, &s.beneficiary, &signers, &2);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    s.client
+        .approve_release(&escrow_id, &s.arbiter, &split(1000, 0, 0));
+}
+
+#[test]
+fn test_invalid_signer_sets_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let escrow_id = create_escrow(&env, &s);
+
+    let unreachable = vec![&env, signer(&s.depositor, 1), signer(&s.beneficiary, 1)];
+    let duplicated = vec![&env, signer(&s.depositor, 1), signer(&s.depositor, 1)];
+    let zero_weight = vec![&env, signer(&s.depositor, 0), signer(&s.beneficiary, 1)];
+    for (signers, threshold) in [
+        (unreachable.clone(), 3),
+        (unreachable, 0),
+        (duplicated, 1),
+        (zero_weight, 1),
+        (Vec::new(&env), 1),
+    ] {
+        assert_eq!(
+            s.client
+                .try_set_signers(&escrow_id, &s.beneficiary, &signers, &threshold),
+            Err(Ok(EscrowError::InvalidSignerSet))
+        );
+    }
+}
+
+#[test]
+fn test_signer_set_needs_depositor_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let escrow_id = create_escrow(&env, &s);
+
+    // A landlord-only set would let the landlord release the deposit alone
+    let signers = vec![&env, signer(&s.beneficiary, 1)];
+    s.client
+        .set_signers(&escrow_id, &s.beneficiary, &signers, &1);
+
+    let signed_by: Vec<Address> =
+        Vec::from_iter(&env, env.auths().iter().map(|(address, _)| address.clone()));
+    assert!(signed_by.contains(&s.beneficiary));
+    assert!(signed_by.contains(&s.depositor));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_signers_fixed_once_funded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    let signers = vec![&env, signer(&s.beneficiary, 2), signer(&s.depositor, 1)];
+    s.client
+        .set_signers(&escrow_id, &s.beneficiary, &signers, &2);
+}
+
+#[test]
+fn test_revoked_approval_no_longer_counts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    let release = split(1000, 0, 0);
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &release);
+    s.client
+        .revoke_approval(&escrow_id, &s.beneficiary, &release);
+    assert!(s.client.get_approvals(&escrow_id).is_empty());
+    assert_eq!(
+        s.client.get_approval_count(&escrow_id, &release),
+        progress(0, 2)
+    );
+
+    // A later approval from another party does not combine with the revoked one
+    s.client.approve_release(&escrow_id, &s.arbiter, &release);
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #28)")]
+fn test_revoke_without_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(1000, 0, 0));
+
+    s.client
+        .revoke_approval(&escrow_id, &s.depositor, &split(1000, 0, 0));
+}
+
+#[test]
+fn test_expired_approvals_stop_counting() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client
+        .set_approval_ttl(&escrow_id, &s.beneficiary, &3_600);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    let release = split(1000, 0, 0);
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &release);
+    advance_time(&env, 1_800);
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(0, 1000, 0));
+    assert_eq!(s.client.get_approvals(&escrow_id).len(), 2);
+
+    advance_time(&env, 1_800);
+    let live = s.client.get_approvals(&escrow_id);
+    assert_eq!(live.len(), 1);
+    assert_eq!(live.get(0).unwrap().signer, s.depositor);
+    assert_eq!(
+        s.client.get_approval_count(&escrow_id, &release),
+        progress(0, 2)
+    );
+
+    // Stale approval neither completes the release nor blocks re-approval
+    s.client.approve_release(&escrow_id, &s.arbiter, &release);
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &release);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_approval_ttl_needs_depositor_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let escrow_id = create_escrow(&env, &s);
+
+    // A short TTL would let the depositor's approvals lapse unnoticed
+    let args = (escrow_id.clone(), s.beneficiary.clone(), 60u64).into_val(&env);
+    mock_beneficiary_only(&env, &s, "set_approval_ttl", args);
+    assert!(s
+        .client
+        .try_set_approval_ttl(&escrow_id, &s.beneficiary, &60)
+        .is_err());
+
+    env.mock_all_auths();
+    s.client.set_approval_ttl(&escrow_id, &s.beneficiary, &60);
+    let signed_by: Vec<Address> =
+        Vec::from_iter(&env, env.auths().iter().map(|(address, _)| address.clone()));
+    assert!(signed_by.contains(&s.depositor));
+}
+
+/// Authorize only the beneficiary's call to `fn_name` with `args`, as if the
+/// depositor refused to co-sign it.
+fn mock_beneficiary_only(env: &Env, s: &Setup, fn_name: &str, args: Vec<Val>) {
+    env.mock_auths(&[MockAuth {
+        address: &s.beneficiary,
+        invoke: &MockAuthInvoke {
+            contract: &s.client.address,
+            fn_name,
+            args,
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+#[test]
+fn test_release_after_needs_depositor_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let escrow_id = create_escrow(&env, &s);
+
+    // Pushing the refund out would keep the deposit locked
+    let args = (escrow_id.clone(), s.beneficiary.clone(), 5_000u64).into_val(&env);
+    mock_beneficiary_only(&env, &s, "set_release_after", args);
+    assert!(s
+        .client
+        .try_set_release_after(&escrow_id, &s.beneficiary, &5_000)
+        .is_err());
+
+    env.mock_all_auths();
+    assert_eq!(s.client.get_release_after(&escrow_id), 0);
+    s.client
+        .set_release_after(&escrow_id, &s.beneficiary, &5_000);
+    let signed_by: Vec<Address> =
+        Vec::from_iter(&env, env.auths().iter().map(|(address, _)| address.clone()));
+    assert!(signed_by.contains(&s.depositor));
+}
+
+fn create_escrow_with_release_after(env: &Env, s: &Setup, release_after: u64) -> BytesN<32> {
+    let escrow_id = create_escrow(env, s);
+    s.client
+        .set_release_after(&escrow_id, &s.beneficiary, &release_after);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    escrow_id
+}
+
+#[test]
+fn test_anyone_triggers_refund_after_release_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let escrow_id = create_escrow_with_release_after(&env, &s, 5_000);
+    assert_eq!(s.client.get_release_after(&escrow_id), 5_000);
+    assert_eq!(
+        s.client.try_trigger_refund(&escrow_id),
+        Err(Ok(EscrowError::RefundNotDue))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    env.set_auths(&[]);
+    s.client.trigger_refund(&escrow_id);
+
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().path,
+        ResolutionPath::Timeout
+    );
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #29)")]
+fn test_trigger_refund_without_release_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    advance_time(&env, 365 * 86_400);
+
+    s.client.trigger_refund(&escrow_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_dispute_blocks_automatic_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow_with_release_after(&env, &s, 5_000);
+    open_statement_dispute(&env, &s, &escrow_id);
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+
+    s.client.trigger_refund(&escrow_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_deduction_claim_blocks_automatic_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow_with_release_after(&env, &s, 5_000);
+    file_move_out_claim(&env, &s, &escrow_id);
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+
+    s.client.trigger_refund(&escrow_id);
+}
+
+/// Funded, disputed escrow with an appeal arbiter and the response window elapsed.
+fn appealable_dispute(env: &Env, s: &Setup) -> (BytesN<32>, Address) {
+    let appeal_arbiter = Address::generate(env);
+    let escrow_id = create_escrow(env, s);
+    s.client
+        .set_appeal_arbiter(&escrow_id, &s.beneficiary, &appeal_arbiter);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(env, s, &escrow_id);
+    advance_time(env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+    (escrow_id, appeal_arbiter)
+}
+
+#[test]
+fn test_unappealed_ruling_settles_after_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, _) = appealable_dispute(&env, &s);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(600, 400, 0));
+    assert!(s.client.is_disputed(&escrow_id));
+    assert_eq!(balance(&env, &s.token, &s.client.address), 1000);
+    assert_eq!(
+        s.client.get_ruling(&escrow_id).unwrap().split,
+        split(600, 400, 0)
+    );
+    assert_eq!(
+        s.client.try_finalize_ruling(&escrow_id),
+        Err(Ok(EscrowError::AppealWindowOpen))
+    );
+
+    advance_time(&env, DEFAULT_APPEAL_WINDOW + 1);
+    s.client.finalize_ruling(&escrow_id);
+
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().path,
+        ResolutionPath::Arbiter
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 600);
+}
+
+#[test]
+fn test_successful_appeal_returns_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, appeal_arbiter) = appealable_dispute(&env, &s);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(600, 400, 0));
+    s.client.appeal_ruling(&escrow_id, &s.depositor);
+
+    // 10% bond on the 1000 deposit
+    assert_eq!(s.client.get_appeal(&escrow_id).unwrap().bond, 100);
+    assert_eq!(
+        balance(&env, &s.token, &s.depositor),
+        INITIAL_BALANCE - 1100
+    );
+    assert_eq!(s.client.get_total_held(&s.token), 1100);
+
+    // Appeal window passing does not let the provisional ruling through
+    advance_time(&env, DEFAULT_APPEAL_WINDOW + 1);
+    assert_eq!(
+        s.client.try_finalize_ruling(&escrow_id),
+        Err(Ok(EscrowError::RulingWindowOpen))
+    );
+
+    s.client
+        .decide_appeal(&escrow_id, &appeal_arbiter, &split(200, 800, 0));
+
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().path,
+        ResolutionPath::Appeal
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 200);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 200);
+    assert_eq!(balance(&env, &s.token, &s.client.address), 0);
+    assert_eq!(s.client.get_total_held(&s.token), 0);
+}
+
+#[test]
+fn test_failed_appeal_forfeits_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, appeal_arbiter) = appealable_dispute(&env, &s);
+    TokenAdminClient::new(&env, &s.token).mint(&s.beneficiary, &500);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(300, 700, 0));
+    s.client.appeal_ruling(&escrow_id, &s.beneficiary);
+    s.client
+        .decide_appeal(&escrow_id, &appeal_arbiter, &split(300, 700, 0));
+
+    // Landlord loses the 100 bond to the tenant
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 500 - 100 + 300);
+    assert_eq!(
+        balance(&env, &s.token, &s.depositor),
+        INITIAL_BALANCE - 300 + 100
+    );
+}
+
+#[test]
+fn test_undecided_appeal_lets_ruling_stand() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, appeal_arbiter) = appealable_dispute(&env, &s);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(600, 400, 0));
+    s.client.appeal_ruling(&escrow_id, &s.depositor);
+
+    // The appeal arbiter never acts; the provisional ruling becomes final
+    advance_time(&env, DEFAULT_DISPUTE_RULING_WINDOW + 1);
+    assert_eq!(
+        s.client
+            .try_decide_appeal(&escrow_id, &appeal_arbiter, &split(0, 1000, 0)),
+        Err(Ok(EscrowError::AppealWindowClosed))
+    );
+    s.client.finalize_ruling(&escrow_id);
+
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().path,
+        ResolutionPath::Arbiter
+    );
+    // Ruled split paid out and the bond returned to the appellant
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 600);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 600);
+    assert_eq!(s.client.get_total_held(&s.token), 0);
+}
+
+#[test]
+fn test_appeal_arbiter_needs_both_parties() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let escrow_id = create_escrow(&env, &s);
+
+    s.client
+        .set_appeal_arbiter(&escrow_id, &s.beneficiary, &Address::generate(&env));
+
+    let signed_by: Vec<Address> =
+        Vec::from_iter(&env, env.auths().iter().map(|(address, _)| address.clone()));
+    assert!(signed_by.contains(&s.beneficiary));
+    assert!(signed_by.contains(&s.depositor));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_appeal_after_window_closed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, _) = appealable_dispute(&env, &s);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(600, 400, 0));
+    advance_time(&env, DEFAULT_APPEAL_WINDOW + 1);
+
+    s.client.appeal_ruling(&escrow_id, &s.depositor);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_only_appeal_arbiter_decides_appeal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, _) = appealable_dispute(&env, &s);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(600, 400, 0));
+    s.client.appeal_ruling(&escrow_id, &s.depositor);
+
+    s.client
+        .decide_appeal(&escrow_id, &s.arbiter, &split(0, 1000, 0));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn test_arbiter_cannot_rule_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, _) = appealable_dispute(&env, &s);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(600, 400, 0));
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(0, 1000, 0));
+}
+
+/// Funded escrow whose disputes go to a 3-arbiter panel drawn from a pool of 4.
+/// Registers a fresh arbiter with the minimum bond.
+fn registered_arbiter(env: &Env, s: &Setup) -> Address {
+    let arbiter = Address::generate(env);
+    TokenAdminClient::new(env, &s.bond_token).mint(&arbiter, &ARBITER_BOND);
+    s.client.register_arbiter(&arbiter, &ARBITER_BOND);
+    arbiter
+}
+
+#[test]
+fn test_panelist_keeps_bond_until_dispute_closes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, pool) = panel_escrow(&env, &s);
+
+    open_statement_dispute(&env, &s, &escrow_id);
+    let panel = s.client.get_panel(&escrow_id).unwrap();
+    let panelist = panel.members.get_unchecked(0);
+    let bystander = pool
+        .iter()
+        .find(|arbiter| !panel.members.contains(arbiter))
+        .unwrap();
+
+    // Replaced panelists are free to leave; their replacement is not
+    s.client.unregister_arbiter(&panelist);
+    advance_time(&env, UNBONDING_PERIOD);
+    assert_eq!(
+        s.client.try_withdraw_arbiter_bond(&panelist),
+        Err(Ok(EscrowError::DisputeActive))
+    );
+    s.client.replace_panelist(&escrow_id, &panelist);
+    s.client.withdraw_arbiter_bond(&panelist);
+
+    s.client.unregister_arbiter(&bystander);
+    advance_time(&env, UNBONDING_PERIOD);
+    assert_eq!(
+        s.client.try_withdraw_arbiter_bond(&bystander),
+        Err(Ok(EscrowError::DisputeActive))
+    );
+
+    s.client.withdraw_dispute(&escrow_id, &s.depositor);
+    s.client.withdraw_arbiter_bond(&bystander);
+    assert_eq!(balance(&env, &s.bond_token, &bystander), ARBITER_BOND);
+}
+
+fn panel_escrow(env: &Env, s: &Setup) -> (BytesN<32>, Vec<Address>) {
+    s.client.update_config(&EscrowConfig {
+        dispute_response_window: 100,
+        dispute_ruling_window: 200,
+        appeal_window: 300,
+        appeal_bond_bps: 1_000,
+    });
+    let mut pool = Vec::new(env);
+    for _ in 0..4 {
+        let arbiter = registered_arbiter(env, s);
+        s.client.add_pool_arbiter(&arbiter);
+        pool.push_back(arbiter);
+    }
+
+    let escrow_id = create_escrow(env, s);
+    s.client.set_panel_size(&escrow_id, &s.beneficiary, &3);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    (escrow_id, pool)
+}
+
+#[test]
+fn test_panel_majority_resolves_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, pool) = panel_escrow(&env, &s);
+
+    open_statement_dispute(&env, &s, &escrow_id);
+    let panel = s.client.get_panel(&escrow_id).unwrap();
+    assert_eq!(panel.members.len(), 3);
+    for (position, member) in panel.members.iter().enumerate() {
+        assert!(pool.contains(&member));
+        assert_eq!(panel.members.first_index_of(&member), Some(position as u32));
+    }
+
+    advance_time(&env, 100);
+    let first = panel.members.get(0).unwrap();
+    let second = panel.members.get(1).unwrap();
+    let third = panel.members.get(2).unwrap();
+    s.client
+        .cast_panel_vote(&escrow_id, &first, &split(500, 500, 0));
+    s.client
+        .cast_panel_vote(&escrow_id, &second, &split(700, 300, 0));
+    assert!(s.client.is_disputed(&escrow_id));
+
+    s.client
+        .cast_panel_vote(&escrow_id, &third, &split(700, 300, 0));
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().path,
+        ResolutionPath::Panel
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 700);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_single_arbiter_cannot_overrule_panel() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, _) = panel_escrow(&env, &s);
+
+    open_statement_dispute(&env, &s, &escrow_id);
+    advance_time(&env, 100);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(0, 1000, 0));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #36)")]
+fn test_only_panelists_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, pool) = panel_escrow(&env, &s);
+
+    open_statement_dispute(&env, &s, &escrow_id);
+    advance_time(&env, 100);
+    let panel = s.client.get_panel(&escrow_id).unwrap();
+    let outsider = pool.iter().find(|a| !panel.members.contains(a)).unwrap();
+
+    s.client
+        .cast_panel_vote(&escrow_id, &outsider, &split(0, 1000, 0));
+}
+
+#[test]
+fn test_replace_silent_panelist() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, pool) = panel_escrow(&env, &s);
+
+    open_statement_dispute(&env, &s, &escrow_id);
+    let panel = s.client.get_panel(&escrow_id).unwrap();
+    let silent = panel.members.get(0).unwrap();
+    let standby = pool.iter().find(|a| !panel.members.contains(a)).unwrap();
+
+    assert_eq!(
+        s.client.try_replace_panelist(&escrow_id, &silent),
+        Err(Ok(EscrowError::RulingWindowOpen))
+    );
+
+    advance_time(&env, 301);
+    s.client.replace_panelist(&escrow_id, &silent);
+
+    let panel = s.client.get_panel(&escrow_id).unwrap();
+    assert_eq!(panel.members.get(0).unwrap(), standby);
+    assert!(!panel.members.contains(&silent));
+    assert_eq!(
+        s.client
+            .get_dispute_info(&escrow_id)
+            .unwrap()
+            .ruling_deadline,
+        env.ledger().timestamp() + 200
+    );
+}
+
+#[test]
+fn test_panel_size_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    s.client.update_config(&EscrowConfig {
+        dispute_response_window: 100,
+        dispute_ruling_window: 200,
+        appeal_window: 300,
+        appeal_bond_bps: 1_000,
+    });
+    s.client.add_pool_arbiter(&registered_arbiter(&env, &s));
+    // Escrow parties never sit on their own panel
+    s.client.add_pool_arbiter(&s.arbiter);
+
+    let escrow_id = create_escrow(&env, &s);
+    assert_eq!(
+        s.client.try_set_panel_size(&escrow_id, &s.beneficiary, &2),
+        Err(Ok(EscrowError::InvalidConfig))
+    );
+    assert_eq!(
+        s.client.try_set_panel_size(&escrow_id, &s.beneficiary, &3),
+        Err(Ok(EscrowError::PoolTooSmall))
+    );
+    s.client.set_panel_size(&escrow_id, &s.beneficiary, &1);
+}
+
+#[test]
+fn test_panel_size_needs_depositor_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    s.client.add_pool_arbiter(&registered_arbiter(&env, &s));
+    let escrow_id = create_escrow(&env, &s);
+
+    // The panel decides how the deposit is split
+    let args = (escrow_id.clone(), s.beneficiary.clone(), 1u32).into_val(&env);
+    mock_beneficiary_only(&env, &s, "set_panel_size", args);
+    assert!(s
+        .client
+        .try_set_panel_size(&escrow_id, &s.beneficiary, &1)
+        .is_err());
+
+    env.mock_all_auths();
+    s.client.set_panel_size(&escrow_id, &s.beneficiary, &1);
+    let signed_by: Vec<Address> =
+        Vec::from_iter(&env, env.auths().iter().map(|(address, _)| address.clone()));
+    assert!(signed_by.contains(&s.depositor));
+}
+
+#[test]
+fn test_pool_takes_only_active_registered_arbiters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    assert_eq!(
+        s.client.try_add_pool_arbiter(&Address::generate(&env)),
+        Err(Ok(EscrowError::ArbiterNotRegistered))
+    );
+
+    let suspended = registered_arbiter(&env, &s);
+    s.client.set_arbiter_active(&suspended, &false);
+    assert_eq!(
+        s.client.try_add_pool_arbiter(&suspended),
+        Err(Ok(EscrowError::ArbiterInactive))
+    );
+
+    // An arbiter deactivated after joining the pool is no longer drawn
+    let pooled = registered_arbiter(&env, &s);
+    s.client.add_pool_arbiter(&pooled);
+    s.client.set_arbiter_active(&pooled, &false);
+    let escrow_id = create_escrow(&env, &s);
+    assert_eq!(
+        s.client.try_set_panel_size(&escrow_id, &s.beneficiary, &1),
+        Err(Ok(EscrowError::PoolTooSmall))
+    );
+}
+
+#[test]
+fn test_panel_rules_without_arbiter_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, _) = panel_escrow(&env, &s);
+
+    open_statement_dispute(&env, &s, &escrow_id);
+    advance_time(&env, 100);
+    let panelist = s
+        .client
+        .get_panel(&escrow_id)
+        .unwrap()
+        .members
+        .get(0)
+        .unwrap();
+
+    assert_eq!(
+        s.client
+            .try_cast_panel_vote(&escrow_id, &panelist, &split(500, 450, 50)),
+        Err(Ok(EscrowError::InvalidArbiterFee))
+    );
+}
+
+#[test]
+fn test_mutual_settlement_during_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(&env, &s, &escrow_id);
+
+    s.client
+        .propose_settlement(&escrow_id, &s.beneficiary, &split(500, 500, 0));
+    // Tenant counters; the new offer replaces the landlord's
+    s.client
+        .propose_settlement(&escrow_id, &s.depositor, &split(350, 650, 0));
+    let offer = s.client.get_settlement_offer(&escrow_id).unwrap();
+    assert_eq!(offer.proposer, s.depositor);
+
+    assert_eq!(
+        s.client
+            .try_accept_settlement(&escrow_id, &s.beneficiary, &split(500, 500, 0)),
+        Err(Ok(EscrowError::OfferMismatch))
+    );
+    s.client
+        .accept_settlement(&escrow_id, &s.beneficiary, &split(350, 650, 0));
+
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().path,
+        ResolutionPath::Mutual
+    );
+    assert_eq!(s.client.get_dispute_info(&escrow_id), None);
+    assert_eq!(s.client.get_settlement_offer(&escrow_id), None);
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 350);
+    assert_eq!(balance(&env, &s.token, &s.arbiter), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_proposer_cannot_accept_own_offer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(&env, &s, &escrow_id);
+
+    s.client
+        .propose_settlement(&escrow_id, &s.depositor, &split(0, 1000, 0));
+    s.client
+        .accept_settlement(&escrow_id, &s.depositor, &split(0, 1000, 0));
+}
+
+#[test]
+fn test_withdraw_dispute_returns_to_funded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(1000, 0, 0));
+    open_statement_dispute(&env, &s, &escrow_id);
+
+    assert_eq!(
+        s.client.try_withdraw_dispute(&escrow_id, &s.beneficiary),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+    s.client.withdraw_dispute(&escrow_id, &s.depositor);
+    assert_event_published(
+        &env,
+        &s.client.address,
+        &DisputeWithdrawn {
+            escrow_id: escrow_id.clone(),
+            withdrawn_by: s.depositor.clone(),
+            category: DisputeCategory::Damage,
+            amount: 1000,
+            status: EscrowStatus::Funded,
+        },
+    );
+
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+    assert_eq!(s.client.get_dispute_info(&escrow_id), None);
+    assert!(s.client.get_approvals(&escrow_id).is_empty());
+
+    // Approvals start over; the pre-dispute approval does not count
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(1000, 0, 0));
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+}
+
+#[test]
+fn test_withdrawn_deduction_dispute_reopens_contested_lines() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let (escrow_id, _) = panel_escrow(&env, &s);
+    file_move_out_claim(&env, &s, &escrow_id);
+    s.client
+        .respond_to_claim(&escrow_id, &s.depositor, &vec![&env, 0]);
+    assert!(s.client.get_panel(&escrow_id).is_some());
+    s.client.withdraw_dispute(&escrow_id, &s.depositor);
+
+    // Only the contested 400 is still held, and it is back on an open claim
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(escrow.funded, 400);
+    assert_eq!(s.client.get_panel(&escrow_id), None);
+    let claim = s.client.get_deduction_claim(&escrow_id).unwrap();
+    assert_eq!(claim.status, ClaimStatus::Open);
+    assert_eq!(
+        claim.items,
+        vec![&env, deduction(&env, DeductionCategory::Damage, 400)]
+    );
+    assert_eq!(
+        s.client
+            .try_file_deduction_claim(&escrow_id, &s.beneficiary, &claim.items),
+        Err(Ok(EscrowError::ClaimAlreadyFiled))
+    );
+
+    s.client
+        .respond_to_claim(&escrow_id, &s.depositor, &vec![&env, 0]);
+
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 550);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 550);
+    assert_eq!(s.client.get_total_held(&s.token), 0);
+}
+
+#[test]
+fn test_withdrawn_deduction_dispute_still_blocks_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow_with_release_after(&env, &s, 5_000);
+    file_move_out_claim(&env, &s, &escrow_id);
+    s.client
+        .respond_to_claim(&escrow_id, &s.depositor, &vec![&env, 0]);
+    s.client.withdraw_dispute(&escrow_id, &s.depositor);
+
+    // The depositor cannot take the contested amount back past the release time
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    assert_eq!(
+        s.client.try_trigger_refund(&escrow_id),
+        Err(Ok(EscrowError::ClaimAlreadyFiled))
+    );
+
+    // Left unanswered, the contested lines go to the landlord
+    let deadline = s
+        .client
+        .get_deduction_claim(&escrow_id)
+        .unwrap()
+        .response_deadline;
+    env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+    s.client.settle_unanswered_claim(&escrow_id);
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 550);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 550);
+}
+
+#[test]
+fn test_create_requires_registered_active_arbiter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let unregistered = Address::generate(&env);
+
+    let agreement_id = String::from_str(&env, "AGREEMENT_001");
+    assert_eq!(
+        s.client.try_create(
+            &agreement_id,
+            &s.depositor,
+            &s.beneficiary,
+            &unregistered,
+            &1000,
+            &s.token,
+        ),
+        Err(Ok(EscrowError::ArbiterNotRegistered))
+    );
+
+    s.client.set_arbiter_active(&s.arbiter, &false);
+    assert_eq!(
+        s.client.try_create(
+            &agreement_id,
+            &s.depositor,
+            &s.beneficiary,
+            &s.arbiter,
+            &1000,
+            &s.token,
+        ),
+        Err(Ok(EscrowError::ArbiterInactive))
+    );
+}
+
+#[test]
+fn test_register_arbiter_stakes_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let profile = s.client.get_arbiter(&s.arbiter);
+    assert_eq!(profile.bond, ARBITER_BOND);
+    assert!(profile.active);
+    assert_eq!(balance(&env, &s.bond_token, &s.arbiter), 0);
+    assert_eq!(
+        balance(&env, &s.bond_token, &s.client.address),
+        ARBITER_BOND
+    );
+
+    let newcomer = Address::generate(&env);
+    TokenAdminClient::new(&env, &s.bond_token).mint(&newcomer, &ARBITER_BOND);
+    assert_eq!(
+        s.client
+            .try_register_arbiter(&newcomer, &(ARBITER_BOND - 1)),
+        Err(Ok(EscrowError::InsufficientBond))
+    );
+    assert_eq!(
+        s.client.try_register_arbiter(&s.arbiter, &ARBITER_BOND),
+        Err(Ok(EscrowError::ArbiterAlreadyRegistered))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #47)")]
+fn test_arbiter_ruling_must_carry_registry_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    s.client
+        .configure_arbiter_registry(&registry_config(&s, 500));
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(&env, &s, &escrow_id);
+    advance_time(&env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(0, 1000, 0));
+}
+
+#[test]
+fn test_reversed_appeal_updates_stats_and_slashing() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let (escrow_id, appeal_arbiter) = appealable_dispute(&env, &s);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(600, 400, 0));
+    assert_eq!(s.client.get_arbiter_stats(&s.arbiter).rulings, 1);
+
+    s.client.appeal_ruling(&escrow_id, &s.depositor);
+    s.client
+        .decide_appeal(&escrow_id, &appeal_arbiter, &split(200, 800, 0));
+
+    let stats = s.client.get_arbiter_stats(&s.arbiter);
+    assert_eq!(stats.appeals_decided, 1);
+    assert_eq!(stats.appeals_reversed, 1);
+    assert_eq!(s.client.get_arbiter_reversal_rate(&s.arbiter), 10_000);
+
+    // Admin slashes part of the bond to the wronged tenant; the arbiter drops below
+    // the minimum bond and is deactivated
+    s.client.slash_arbiter(&s.arbiter, &200, &s.depositor);
+    let profile = s.client.get_arbiter(&s.arbiter);
+    assert_eq!(profile.bond, ARBITER_BOND - 200);
+    assert!(!profile.active);
+    assert_eq!(balance(&env, &s.bond_token, &s.depositor), 200);
+    assert_eq!(s.client.get_total_held(&s.bond_token), ARBITER_BOND - 200);
+}
+
+#[test]
+fn test_unregistered_arbiter_withdraws_bond_after_unbonding() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    assert_eq!(
+        s.client.try_withdraw_arbiter_bond(&s.arbiter),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    s.client.unregister_arbiter(&s.arbiter);
+    let profile = s.client.get_arbiter(&s.arbiter);
+    assert!(!profile.active);
+    assert_eq!(
+        profile.unbonds_at,
+        env.ledger().timestamp() + UNBONDING_PERIOD
+    );
+    assert_eq!(
+        s.client.try_set_arbiter_active(&s.arbiter, &true),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    advance_time(&env, UNBONDING_PERIOD - 1);
+    assert_eq!(
+        s.client.try_withdraw_arbiter_bond(&s.arbiter),
+        Err(Ok(EscrowError::RefundNotDue))
+    );
+
+    advance_time(&env, 1);
+    s.client.withdraw_arbiter_bond(&s.arbiter);
+    assert_eq!(balance(&env, &s.bond_token, &s.arbiter), ARBITER_BOND);
+    assert_eq!(s.client.get_total_held(&s.bond_token), 0);
+    assert_eq!(
+        s.client.try_get_arbiter(&s.arbiter),
+        Err(Ok(EscrowError::ArbiterNotRegistered))
+    );
+}
+
+#[test]
+fn test_appeal_decision_must_carry_registry_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    s.client
+        .configure_arbiter_registry(&registry_config(&s, 500));
+    let (escrow_id, appeal_arbiter) = appealable_dispute(&env, &s);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(550, 400, 50));
+    s.client.appeal_ruling(&escrow_id, &s.depositor);
+    assert_eq!(
+        s.client
+            .try_decide_appeal(&escrow_id, &appeal_arbiter, &split(0, 800, 200)),
+        Err(Ok(EscrowError::InvalidArbiterFee))
+    );
+
+    s.client
+        .decide_appeal(&escrow_id, &appeal_arbiter, &split(150, 800, 50));
+    assert_eq!(balance(&env, &s.token, &s.arbiter), 50);
+}
+
+#[test]
+fn test_arbiter_keeps_bond_while_deciding_open_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    open_statement_dispute(&env, &s, &escrow_id);
+    advance_time(&env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+
+    s.client.unregister_arbiter(&s.arbiter);
+    advance_time(&env, UNBONDING_PERIOD);
+    assert_eq!(
+        s.client.try_withdraw_arbiter_bond(&s.arbiter),
+        Err(Ok(EscrowError::DisputeActive))
+    );
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(600, 400, 0));
+    s.client.withdraw_arbiter_bond(&s.arbiter);
+    assert_eq!(balance(&env, &s.bond_token, &s.arbiter), ARBITER_BOND);
+
+    // The ruling record outlives the profile
+    assert_eq!(
+        s.client.try_get_arbiter(&s.arbiter),
+        Err(Ok(EscrowError::ArbiterNotRegistered))
+    );
+    assert_eq!(s.client.get_arbiter_stats(&s.arbiter).rulings, 1);
+}
+
+fn milestone(env: &Env, byte: u8, amount: i128, due_date: u64) -> Milestone {
+    Milestone {
+        description_hash: evidence_hash(env, byte),
+        amount,
+        due_date,
+    }
+}
+
+/// Funded escrow released in three milestones of 300, 300 and 400.
+fn milestone_escrow(env: &Env, s: &Setup) -> BytesN<32> {
+    let escrow_id = s.client.create_milestone_escrow(
+        &String::from_str(env, "AGREEMENT_001"),
+        &s.depositor,
+        &s.beneficiary,
+        &s.arbiter,
+        &s.token,
+        &vec![
+            env,
+            milestone(env, 1, 300, 1_000),
+            milestone(env, 2, 300, 2_000),
+            milestone(env, 3, 400, 3_000),
+        ],
+    );
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    escrow_id
+}
+
+#[test]
+fn test_milestones_release_in_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = milestone_escrow(&env, &s);
+    assert_eq!(s.client.get_escrow(&escrow_id).amount, 1000);
+    assert_eq!(
+        balance(&env, &s.token, &s.depositor),
+        INITIAL_BALANCE - 1000
+    );
+
+    // A split must cover the current milestone only
+    assert_eq!(
+        s.client
+            .try_approve_release(&escrow_id, &s.beneficiary, &split(1000, 0, 0)),
+        Err(Ok(EscrowError::InvalidSplit))
+    );
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(300, 0, 0));
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(300, 0, 0));
+
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(escrow.amount, 700);
+    assert_eq!(s.client.get_milestones_settled(&escrow_id), 1);
+    assert_eq!(s.client.get_settlement(&escrow_id), None);
+    assert_eq!(
+        s.client
+            .get_milestone_settlement(&escrow_id, &0)
+            .unwrap()
+            .path,
+        ResolutionPath::Multisig
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 300);
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(300, 0, 0));
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(300, 0, 0));
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(0, 400, 0));
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(0, 400, 0));
+
+    // Done once every milestone is settled
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.amount, 0);
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().split,
+        split(600, 400, 0)
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 600);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 600);
+    assert_eq!(s.client.get_total_held(&s.token), 0);
+}
+
+#[test]
+fn test_milestone_dispute_settles_that_milestone() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = milestone_escrow(&env, &s);
+    open_statement_dispute(&env, &s, &escrow_id);
+    advance_time(&env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+
+    assert_eq!(
+        s.client
+            .try_resolve_dispute(&escrow_id, &s.arbiter, &split(500, 500, 0)),
+        Err(Ok(EscrowError::InvalidSplit))
+    );
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(100, 200, 0));
+
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+    assert_eq!(s.client.get_dispute_info(&escrow_id), None);
+    assert_eq!(s.client.get_milestones_settled(&escrow_id), 1);
+
+    // The next milestone can be disputed afresh
+    open_statement_dispute(&env, &s, &escrow_id);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Disputed
+    );
+}
+
+#[test]
+fn test_milestone_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let agreement_id = String::from_str(&env, "AGREEMENT_001");
+    for milestones in [
+        Vec::new(&env),
+        vec![&env, milestone(&env, 1, 0, 1_000)],
+        vec![
+            &env,
+            milestone(&env, 1, 300, 2_000),
+            milestone(&env, 2, 300, 1_000),
+        ],
+    ] {
+        assert_eq!(
+            s.client.try_create_milestone_escrow(
+                &agreement_id,
+                &s.depositor,
+                &s.beneficiary,
+                &s.arbiter,
+                &s.token,
+                &milestones,
+            ),
+            Err(Ok(EscrowError::InvalidMilestones))
+        );
+    }
+
+    // Milestone escrows take no deduction claims
+    let escrow_id = milestone_escrow(&env, &s);
+    assert_eq!(s.client.get_milestones(&escrow_id).len(), 3);
+    assert_eq!(
+        s.client.try_file_deduction_claim(
+            &escrow_id,
+            &s.beneficiary,
+            &vec![&env, deduction(&env, DeductionCategory::Cleaning, 100)],
+        ),
+        Err(Ok(EscrowError::InvalidState))
+    );
+}
+
+mod mock_vault {
+    use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env};
+
+    use crate::vault::Vault;
+
+    #[contracttype]
+    enum VaultKey {
+        Token,
+        Shares(Address),
+        TotalShares,
+    }
+
+    /// Single-token vault whose yield is whatever is sent to it beyond deposits.
+    #[contract]
+    pub struct MockVault;
+
+    #[contractimpl]
+    impl MockVault {
+        pub fn __constructor(env: Env, token: Address) {
+            env.storage().instance().set(&VaultKey::Token, &token);
+        }
+    }
+
+    fn token(env: &Env) -> token::Client<'_> {
+        let token: Address = env.storage().instance().get(&VaultKey::Token).unwrap();
+        token::Client::new(env, &token)
+    }
+
+    fn shares_of(env: &Env, owner: &Address) -> i128 {
+        let key = VaultKey::Shares(owner.clone());
+        env.storage().instance().get(&key).unwrap_or_default()
+    }
+
+    fn total_shares(env: &Env) -> i128 {
+        let key = VaultKey::TotalShares;
+        env.storage().instance().get(&key).unwrap_or_default()
+    }
+
+    fn set_shares(env: &Env, owner: &Address, shares: i128, total: i128) {
+        let key = VaultKey::Shares(owner.clone());
+        env.storage().instance().set(&key, &shares);
+        env.storage().instance().set(&VaultKey::TotalShares, &total);
+    }
+
+    #[contractimpl]
+    impl Vault for MockVault {
+        fn deposit(env: Env, from: Address, amount: i128) -> i128 {
+            from.require_auth();
+            let vault = env.current_contract_address();
+            let assets = token(&env).balance(&vault);
+            let total = total_shares(&env);
+            let minted = if total == 0 {
+                amount
+            } else {
+                amount * total / assets
+            };
+            token(&env).transfer(&from, &vault, &amount);
+            set_shares(&env, &from, shares_of(&env, &from) + minted, total + minted);
+            minted
+        }
+
+        fn withdraw(env: Env, to: Address, shares: i128) -> i128 {
+            to.require_auth();
+            let vault = env.current_contract_address();
+            let total = total_shares(&env);
+            let amount = shares * token(&env).balance(&vault) / total;
+            set_shares(&env, &to, shares_of(&env, &to) - shares, total - shares);
+            token(&env).transfer(&vault, &to, &amount);
+            amount
+        }
+
+        fn balance(env: Env, owner: Address) -> i128 {
+            let total = total_shares(&env);
+            if total == 0 {
+                return 0;
+            }
+            let vault = env.current_contract_address();
+            shares_of(&env, &owner) * token(&env).balance(&vault) / total
+        }
+    }
+}
+
+fn yield_policy(
+    vault: &Address,
+    tenant_bps: u32,
+    landlord_bps: u32,
+    platform_bps: u32,
+) -> YieldPolicy {
+    YieldPolicy {
+        vault: vault.clone(),
+        tenant_bps,
+        landlord_bps,
+        platform_bps,
+    }
+}
+
+#[test]
+fn test_vaulted_deposit_shares_yield_on_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let vault = env.register(mock_vault::MockVault, (s.token.clone(),));
+    let vault_client = crate::VaultClient::new(&env, &vault);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.set_yield_policy(
+        &escrow_id,
+        &s.beneficiary,
+        &yield_policy(&vault, 5_000, 3_000, 2_000),
+    );
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    // The deposit sits in the vault, outside the contract's custody total
+    assert_eq!(balance(&env, &s.token, &vault), 1000);
+    assert_eq!(s.client.get_total_held(&s.token), 0);
+    assert_eq!(
+        s.client.get_vault_position(&escrow_id),
+        Some(VaultPosition {
+            principal: 1000,
+            shares: 1000,
+        })
+    );
+
+    // The vault earns 101 over the lease
+    TokenAdminClient::new(&env, &s.token).mint(&vault, &101);
+    assert_eq!(vault_client.balance(&s.client.address), 1101);
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(800, 200, 0));
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(800, 200, 0));
+
+    // Principal follows the split; yield goes 50/30/20, rounding dust to the platform
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 800 + 30);
+    assert_eq!(
+        balance(&env, &s.token, &s.depositor),
+        INITIAL_BALANCE - 1000 + 200 + 50
+    );
+    assert_eq!(balance(&env, &s.token, &s.admin), 21);
+    assert_eq!(balance(&env, &s.token, &vault), 0);
+    assert_eq!(s.client.get_vault_position(&escrow_id), None);
+    assert_eq!(s.client.get_total_held(&s.token), 0);
+}
+
+#[test]
+fn test_yield_policy_needs_depositor_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let vault = env.register(mock_vault::MockVault, (s.token.clone(),));
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.set_yield_policy(
+        &escrow_id,
+        &s.beneficiary,
+        &yield_policy(&vault, 10_000, 0, 0),
+    );
+
+    let signed_by: Vec<Address> =
+        Vec::from_iter(&env, env.auths().iter().map(|(address, _)| address.clone()));
+    assert!(signed_by.contains(&s.beneficiary));
+    assert!(signed_by.contains(&s.depositor));
+}
+
+#[test]
+fn test_vault_loss_settles_on_returned_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let vault = env.register(mock_vault::MockVault, (s.token.clone(),));
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.set_yield_policy(
+        &escrow_id,
+        &s.beneficiary,
+        &yield_policy(&vault, 5_000, 3_000, 2_000),
+    );
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    // The vault loses 200 of the deposit
+    TokenClient::new(&env, &s.token).burn(&vault, &200);
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(800, 200, 0));
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(800, 200, 0));
+
+    // The 800 that came back is split pro rata and the loss is recorded
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 640);
+    assert_eq!(
+        balance(&env, &s.token, &s.depositor),
+        INITIAL_BALANCE - 1000 + 160
+    );
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().split,
+        split(640, 160, 0)
+    );
+    assert_eq!(s.client.get_vault_loss(&escrow_id), 200);
+    assert_eq!(s.client.get_total_held(&s.token), 0);
+}
+
+#[test]
+fn test_yield_policy_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let vault = env.register(mock_vault::MockVault, (s.token.clone(),));
+
+    let escrow_id = create_escrow(&env, &s);
+    assert_eq!(
+        s.client.try_set_yield_policy(
+            &escrow_id,
+            &s.beneficiary,
+            &yield_policy(&vault, 5_000, 5_000, 1),
+        ),
+        Err(Ok(EscrowError::InvalidConfig))
+    );
+    assert_eq!(
+        s.client.try_set_yield_policy(
+            &escrow_id,
+            &s.depositor,
+            &yield_policy(&vault, 10_000, 0, 0),
+        ),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+
+    // Plain escrows keep their funds in the contract
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    assert_eq!(s.client.get_yield_policy(&escrow_id), None);
+    assert_eq!(s.client.get_total_held(&s.token), 1000);
+    assert_eq!(
+        s.client.try_set_yield_policy(
+            &escrow_id,
+            &s.beneficiary,
+            &yield_policy(&vault, 10_000, 0, 0),
+        ),
+        Err(Ok(EscrowError::InvalidState))
+    );
+}
+
+fn installment(amount: i128, due_date: u64) -> Installment {
+    Installment { amount, due_date }
+}
+
+#[test]
+fn test_installments_fund_escrow_to_target() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.set_installments(
+        &escrow_id,
+        &s.beneficiary,
+        &vec![
+            &env,
+            installment(400, 1_000),
+            installment(300, 2_000),
+            installment(300, 3_000),
+        ],
+    );
+
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!((escrow.funded, escrow.amount), (400, 1000));
+    assert_eq!(escrow.status, EscrowStatus::Pending);
+
+    // Nothing can be released before the target is met
+    assert_eq!(
+        s.client
+            .try_approve_release(&escrow_id, &s.beneficiary, &split(400, 0, 0)),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.funded, 1000);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(
+        balance(&env, &s.token, &s.depositor),
+        INITIAL_BALANCE - 1000
+    );
+    assert_eq!(
+        s.client.try_fund_escrow(&escrow_id, &s.depositor),
+        Err(Ok(EscrowError::InvalidState))
+    );
+}
+
+#[test]
+fn test_partly_funded_escrow_refunds_paid_installments() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client
+        .set_release_after(&escrow_id, &s.beneficiary, &5_000);
+    assert_eq!(
+        s.client.try_set_installments(
+            &escrow_id,
+            &s.beneficiary,
+            &vec![&env, installment(400, 1_000), installment(500, 2_000)],
+        ),
+        Err(Ok(EscrowError::InvalidConfig))
+    );
+    s.client.set_installments(
+        &escrow_id,
+        &s.beneficiary,
+        &vec![&env, installment(400, 1_000), installment(600, 2_000)],
+    );
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    s.client.trigger_refund(&escrow_id);
+
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().split,
+        split(0, 400, 0)
+    );
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE);
+}
+
+#[test]
+fn test_overdue_installment_allows_early_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.set_installments(
+        &escrow_id,
+        &s.beneficiary,
+        &vec![&env, installment(400, 1_000), installment(600, 2_000)],
+    );
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    // Paid on schedule: nothing to act on before the release-after time
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(
+        s.client.try_trigger_refund(&escrow_id),
+        Err(Ok(EscrowError::RefundNotDue))
+    );
+
+    // The second installment is missed
+    env.ledger().with_mut(|li| li.timestamp = 2_001);
+    s.client.trigger_refund(&escrow_id);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().split,
+        split(0, 400, 0)
+    );
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE);
+}
+
+#[test]
+fn test_late_installment_keeps_escrow_until_refunded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.set_installments(
+        &escrow_id,
+        &s.beneficiary,
+        &vec![&env, installment(400, 1_000), installment(600, 2_000)],
+    );
+
+    // Nothing paid in yet, so a missed first installment has nothing to refund
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    assert_eq!(
+        s.client.try_trigger_refund(&escrow_id),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    // Paid late but in full before anyone refunded: the escrow stands
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+    assert_eq!(
+        s.client.try_trigger_refund(&escrow_id),
+        Err(Ok(EscrowError::RefundNotDue))
+    );
+}
+
+#[test]
+fn test_deposit_adjustments_need_depositor_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    // Rent went up: landlord asks for a top-up
+    s.client
+        .request_adjustment(&escrow_id, &s.beneficiary, &1200);
+    assert_eq!(
+        s.client
+            .try_request_adjustment(&escrow_id, &s.beneficiary, &1300),
+        Err(Ok(EscrowError::AdjustmentPending))
+    );
+    assert_eq!(
+        s.client.try_approve_adjustment(&escrow_id, &s.beneficiary),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+    assert_eq!(s.client.get_escrow(&escrow_id).funded, 1000);
+
+    s.client.approve_adjustment(&escrow_id, &s.depositor);
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!((escrow.funded, escrow.amount), (1200, 1200));
+    assert_eq!(s.client.get_adjustment(&escrow_id), None);
+    assert_eq!(s.client.get_total_held(&s.token), 1200);
+
+    // A reduction is refunded once approved; a rejected request changes nothing
+    s.client
+        .request_adjustment(&escrow_id, &s.beneficiary, &900);
+    s.client.approve_adjustment(&escrow_id, &s.depositor);
+    s.client
+        .request_adjustment(&escrow_id, &s.beneficiary, &1500);
+    s.client.reject_adjustment(&escrow_id, &s.depositor);
+    assert_eq!(s.client.get_escrow(&escrow_id).funded, 900);
+    assert_eq!(balance(&env, &s.token, &s.depositor), INITIAL_BALANCE - 900);
+
+    // Payouts cover the funded amount
+    assert_eq!(
+        s.client
+            .try_approve_release(&escrow_id, &s.beneficiary, &split(1000, 0, 0)),
+        Err(Ok(EscrowError::InvalidSplit))
+    );
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(900, 0, 0));
+    s.client
+        .approve_release(&escrow_id, &s.depositor, &split(900, 0, 0));
+    assert_eq!(balance(&env, &s.token, &s.beneficiary), 900);
+}
+
+#[test]
+fn test_partly_funded_refund_pays_only_what_was_paid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    // Milestone escrows cannot be funded in installments
+    let milestone_id = s.client.create_milestone_escrow(
+        &String::from_str(&env, "AGREEMENT_002"),
+        &s.depositor,
+        &s.beneficiary,
+        &s.arbiter,
+        &s.token,
+        &vec![
+            &env,
+            milestone(&env, 1, 600, 1_000),
+            milestone(&env, 2, 400, 2_000),
+        ],
+    );
+    assert_eq!(
+        s.client.try_set_installments(
+            &milestone_id,
+            &s.beneficiary,
+            &vec![&env, installment(300, 1_000), installment(700, 2_000)],
+        ),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    // Another escrow's funds sit in the same contract
+    let other_id = create_escrow_for(&env, &s, "AGREEMENT_003");
+    s.client.fund_escrow(&other_id, &s.depositor);
+
+    let escrow_id = create_escrow(&env, &s);
+    s.client
+        .set_release_after(&escrow_id, &s.beneficiary, &5_000);
+    s.client.set_installments(
+        &escrow_id,
+        &s.beneficiary,
+        &vec![&env, installment(300, 1_000), installment(700, 2_000)],
+    );
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    s.client.trigger_refund(&escrow_id);
+    assert_eq!(
+        s.client.try_trigger_refund(&escrow_id),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    assert_eq!(
+        s.client.get_settlement(&escrow_id).unwrap().split,
+        split(0, 300, 0)
+    );
+    assert_eq!(
+        balance(&env, &s.token, &s.depositor),
+        INITIAL_BALANCE - 1000
+    );
+    assert_eq!(s.client.get_total_held(&s.token), 1000);
+}
+
+fn create_escrow_for(env: &Env, s: &Setup, agreement_id: &str) -> BytesN<32> {
+    s.client.create(
+        &String::from_str(env, agreement_id),
+        &s.depositor,
+        &s.beneficiary,
+        &s.arbiter,
+        &1000,
+        &s.token,
+    )
+}
+
+#[test]
+fn test_escrows_listed_by_party_and_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let pending = create_escrow_for(&env, &s, "AGREEMENT_001");
+    let funded = create_escrow_for(&env, &s, "AGREEMENT_002");
+    let disputed = create_escrow_for(&env, &s, "AGREEMENT_003");
+    s.client.fund_escrow(&funded, &s.depositor);
+    s.client.fund_escrow(&disputed, &s.depositor);
+    open_statement_dispute(&env, &s, &disputed);
+
+    // Party index pages in creation order
+    let first = s.client.get_escrows_by_party(&s.arbiter, &0, &2);
+    assert_eq!(first.escrows.len(), 2);
+    assert_eq!(first.escrows.get(0).unwrap().id, pending);
+    assert_eq!(first.next_cursor, Some(2));
+    let second = s.client.get_escrows_by_party(&s.arbiter, &2, &2);
+    assert_eq!(second.escrows.get(0).unwrap().id, disputed);
+    assert_eq!(second.next_cursor, None);
+    assert!(s
+        .client
+        .get_escrows_by_party(&Address::generate(&env), &0, &10)
+        .escrows
+        .is_empty());
+
+    let ids = |status: EscrowStatus| -> Vec<BytesN<32>> {
+        let mut ids = Vec::new(&env);
+        for escrow in s.client.get_escrows_by_status(&status, &0, &10).escrows {
+            ids.push_back(escrow.id);
+        }
+        ids
+    };
+    assert_eq!(ids(EscrowStatus::Pending), vec![&env, pending.clone()]);
+    assert_eq!(ids(EscrowStatus::Funded), vec![&env, funded.clone()]);
+    assert_eq!(ids(EscrowStatus::Disputed), vec![&env, disputed.clone()]);
+
+    // Open disputes for the arbiter
+    let open = s
+        .client
+        .get_party_escrows_by_status(&s.arbiter, &EscrowStatus::Disputed, &0, &10);
+    assert_eq!(open.escrows.len(), 1);
+    assert_eq!(open.escrows.get(0).unwrap().id, disputed);
+
+    // Indexes follow every transition
+    advance_time(&env, DEFAULT_DISPUTE_RESPONSE_WINDOW);
+    s.client
+        .resolve_dispute(&disputed, &s.arbiter, &split(1000, 0, 0));
+    assert!(ids(EscrowStatus::Disputed).is_empty());
+    assert_eq!(ids(EscrowStatus::Released), vec![&env, disputed]);
+}
+
+#[test]
+fn test_status_index_fills_gaps_left_by_transitions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let first = create_escrow_for(&env, &s, "AGREEMENT_001");
+    let second = create_escrow_for(&env, &s, "AGREEMENT_002");
+    let third = create_escrow_for(&env, &s, "AGREEMENT_003");
+
+    let ids = |status: EscrowStatus, cursor: u32, limit: u32| -> Vec<BytesN<32>> {
+        let mut ids = Vec::new(&env);
+        for escrow in s
+            .client
+            .get_escrows_by_status(&status, &cursor, &limit)
+            .escrows
+        {
+            ids.push_back(escrow.id);
+        }
+        ids
+    };
+
+    // The last Pending escrow moves into the slot the first one leaves
+    s.client.fund_escrow(&first, &s.depositor);
+    assert_eq!(
+        ids(EscrowStatus::Pending, 0, 10),
+        vec![&env, third.clone(), second.clone()]
+    );
+    assert_eq!(ids(EscrowStatus::Pending, 1, 1), vec![&env, second.clone()]);
+
+    s.client.fund_escrow(&third, &s.depositor);
+    s.client.fund_escrow(&second, &s.depositor);
+    assert!(ids(EscrowStatus::Pending, 0, 10).is_empty());
+    assert_eq!(
+        ids(EscrowStatus::Funded, 0, 10),
+        vec![&env, first, third, second]
+    );
+    let page = s
+        .client
+        .get_escrows_by_status(&EscrowStatus::Funded, &0, &2);
+    assert_eq!(page.next_cursor, Some(2));
+}
+
+#[test]
+fn test_lifecycle_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let contract = s.client.address.clone();
+
+    let escrow_id = create_escrow(&env, &s);
+    assert_event_published(
+        &env,
+        &contract,
+        &EscrowCreated {
+            escrow_id: escrow_id.clone(),
+            agreement_id: String::from_str(&env, "AGREEMENT_001"),
+            depositor: s.depositor.clone(),
+            beneficiary: s.beneficiary.clone(),
+            arbiter: s.arbiter.clone(),
+            token: s.token.clone(),
+            amount: 1000,
+            status: EscrowStatus::Pending,
+        },
+    );
+
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    assert_event_published(
+        &env,
+        &contract,
+        &EscrowFunded {
+            escrow_id: escrow_id.clone(),
+            depositor: s.depositor.clone(),
+            payment: 1000,
+            funded: 1000,
+            amount: 1000,
+            status: EscrowStatus::Funded,
+        },
+    );
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &split(1000, 0, 0));
+    assert_event_published(
+        &env,
+        &contract,
+        &ApprovalAdded {
+            escrow_id: escrow_id.clone(),
+            signer: s.beneficiary.clone(),
+            beneficiary_amount: 1000,
+            depositor_amount: 0,
+            arbiter_fee: 0,
+            approved_weight: 1,
+            threshold: 2,
+            status: EscrowStatus::Funded,
+        },
+    );
+
+    s.client
+        .revoke_approval(&escrow_id, &s.beneficiary, &split(1000, 0, 0));
+    assert_event_published(
+        &env,
+        &contract,
+        &ApprovalRevoked {
+            escrow_id: escrow_id.clone(),
+            signer: s.beneficiary.clone(),
+            beneficiary_amount: 1000,
+            depositor_amount: 0,
+            arbiter_fee: 0,
+            status: EscrowStatus::Funded,
+        },
+    );
+
+    open_statement_dispute(&env, &s, &escrow_id);
+    assert_event_published(
+        &env,
+        &contract,
+        &DisputeOpened {
+            escrow_id: escrow_id.clone(),
+            opened_by: s.depositor.clone(),
+            category: DisputeCategory::Damage,
+            amount: 1000,
+            response_deadline: DEFAULT_DISPUTE_RESPONSE_WINDOW,
+            status: EscrowStatus::Disputed,
+        },
+    );
+
+    s.client.submit_evidence(
+        &escrow_id,
+        &s.beneficiary,
+        &evidence_hash(&env, 7),
+        &String::from_str(&env, "ipfs://move-in-photos"),
+    );
+    assert_event_published(
+        &env,
+        &contract,
+        &EvidenceAdded {
+            escrow_id: escrow_id.clone(),
+            submitter: s.beneficiary.clone(),
+            content_hash: evidence_hash(&env, 7),
+            evidence_count: 1,
+            status: EscrowStatus::Disputed,
+        },
+    );
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &split(600, 400, 0));
+    assert_event_published(
+        &env,
+        &contract,
+        &DisputeResolved {
+            escrow_id: escrow_id.clone(),
+            resolved_by: s.arbiter.clone(),
+            beneficiary_amount: 600,
+            depositor_amount: 400,
+            arbiter_fee: 0,
+            path: ResolutionPath::Arbiter,
+            status: EscrowStatus::Released,
+        },
+    );
+    assert_event_published(
+        &env,
+        &contract,
+        &EscrowSettled {
+            escrow_id,
+            beneficiary: s.beneficiary.clone(),
+            beneficiary_amount: 600,
+            depositor: s.depositor.clone(),
+            depositor_amount: 400,
+            arbiter: s.arbiter.clone(),
+            arbiter_fee: 0,
+            path: ResolutionPath::Arbiter,
+            status: EscrowStatus::Released,
+        },
+    );
+}
@@ -1,5 +1,5 @@
 //! Data structures and enums for the Escrow contract.
-use soroban_sdk::{contracttype, Address, BytesN, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 /// Status of an escrow throughout its lifecycle.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -21,38 +21,425 @@ pub enum EscrowStatus {
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
 pub struct Escrow {
-    /// Unique identifier for the escrow (hash of agreement_id)
+    /// Unique identifier for the escrow (hash of agreement_id, depositor and escrow count)
     pub id: BytesN<32>,
+    /// Rent agreement this escrow secures
+    pub agreement_id: String,
     /// The party depositing funds (tenant)
     pub depositor: Address,
     /// The party who benefits from the deposit (landlord)
     pub beneficiary: Address,
     /// The admin/arbiter who can resolve disputes
     pub arbiter: Address,
-    /// Amount of funds in the escrow
+    /// Target amount of the escrow (reduced when part of it is released early)
     pub amount: i128,
+    /// Amount the depositor has paid in so far; equals `amount` once Funded
+    pub funded: i128,
     /// Token contract address (USDC, XLM, etc.)
     pub token: Address,
     /// Current status of the escrow
     pub status: EscrowStatus,
     /// Timestamp when escrow was created
     pub created_at: u64,
-    /// Reason for dispute, if any
-    pub dispute_reason: Option<String>,
+}
+
+/// One stage of a milestone escrow, released separately and in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Milestone {
+    /// Hash of the off-chain description of the work or condition
+    pub description_hash: BytesN<32>,
+    /// Amount released for this milestone
+    pub amount: i128,
+    /// Timestamp the milestone is due by
+    pub due_date: u64,
+}
+
+/// One page of escrows from an index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct EscrowPage {
+    /// Escrows on this page, in index order
+    pub escrows: Vec<Escrow>,
+    /// Cursor to pass for the next page; None when this is the last page
+    pub next_cursor: Option<u32>,
+}
+
+/// One scheduled payment towards a Pending escrow's target amount.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Installment {
+    /// Amount due in this installment
+    pub amount: i128,
+    /// Timestamp the installment is due by
+    pub due_date: u64,
+}
+
+/// Beneficiary's request to change a Funded escrow's amount, pending the
+/// depositor's approval.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct AdjustmentRequest {
+    /// Requested new escrow amount
+    pub new_amount: i128,
+    /// Timestamp of the request
+    pub requested_at: u64,
+}
+
+/// Why an escrow was frozen for arbiter resolution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum DisputeReason {
+    /// Free-form statement from the disputing party
+    Statement(String),
+    /// Deduction claim lines contested by the depositor (indices into the claim items)
+    ContestedDeductions(Vec<u32>),
+}
+
+/// What a dispute is about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum DisputeCategory {
+    Damage = 0,
+    Cleaning = 1,
+    UnpaidRent = 2,
+    Utilities = 3,
+    ContestedDeductions = 4,
+    Other = 5,
+}
+
+/// A piece of evidence attached to a dispute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Evidence {
+    /// Party that submitted the evidence
+    pub submitter: Address,
+    /// Hash of the off-chain content (photos, receipts, messages)
+    pub content_hash: BytesN<32>,
+    /// Where the content can be retrieved (IPFS, HTTPS)
+    pub uri: String,
+    /// Timestamp of submission
+    pub submitted_at: u64,
+}
+
+/// Dispute record kept while an escrow is Disputed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Dispute {
+    /// Party that opened the dispute
+    pub opened_by: Address,
+    /// Timestamp when the dispute was opened
+    pub opened_at: u64,
+    /// What the dispute is about
+    pub category: DisputeCategory,
+    /// Why the escrow was frozen
+    pub reason: DisputeReason,
+    /// Append-only evidence log from both parties
+    pub evidence: Vec<Evidence>,
+    /// Until this timestamp the arbiter may not rule unless the counter-party has responded
+    pub response_deadline: u64,
+    /// After this timestamp an unresolved dispute may be escalated to the admin
+    pub ruling_deadline: u64,
+    /// Whether the dispute was escalated and now awaits an admin ruling
+    pub escalated: bool,
+}
+
+/// Category of a deposit deduction line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum DeductionCategory {
+    Cleaning = 0,
+    Damage = 1,
+    UnpaidRent = 2,
+    Utilities = 3,
+    KeysAndLocks = 4,
+    Other = 5,
+}
+
+/// A single line of a deduction claim.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct DeductionItem {
+    /// What the deduction is for
+    pub category: DeductionCategory,
+    /// Amount claimed from the deposit
+    pub amount: i128,
+    /// Hash of off-chain evidence (photos, receipts)
+    pub evidence_hash: BytesN<32>,
+}
+
+/// Status of a deduction claim.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum ClaimStatus {
+    /// Awaiting the depositor's response
+    Open = 0,
+    /// Every line accepted (explicitly or by letting the window lapse)
+    Accepted = 1,
+    /// Some lines accepted; the rest went to dispute
+    PartiallyAccepted = 2,
+    /// Every line contested and sent to dispute
+    Contested = 3,
+}
+
+/// Itemized deduction claim filed by the beneficiary at lease end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct DeductionClaim {
+    /// Claimed deduction lines
+    pub items: Vec<DeductionItem>,
+    /// Indices of lines accepted by the depositor
+    pub accepted_lines: Vec<u32>,
+    /// Current status of the claim
+    pub status: ClaimStatus,
+    /// Timestamp when the claim was filed
+    pub filed_at: u64,
+    /// Last timestamp at which the depositor may respond
+    pub response_deadline: u64,
+}
+
+/// How an escrow reached its final payout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum ResolutionPath {
+    /// Released by matching approvals from the escrow parties
+    Multisig = 0,
+    /// Released by the arbiter resolving a dispute
+    Arbiter = 1,
+    /// Released by the depositor accepting a deduction claim
+    DeductionClaim = 2,
+    /// Released by the admin ruling on an escalated dispute
+    Admin = 3,
+    /// Refunded to the depositor after the release-after time passed uncontested
+    Timeout = 4,
+    /// Released by the appeal arbiter deciding an appealed ruling
+    Appeal = 5,
+    /// Released by majority vote of an arbiter panel
+    Panel = 6,
+    /// Released by the primary parties agreeing a settlement during a dispute
+    Mutual = 7,
+}
+
+/// Division of escrowed funds between the parties.
+/// The three amounts must add up to the escrowed amount.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ReleaseSplit {
+    /// Amount paid to the beneficiary (landlord)
+    pub beneficiary_amount: i128,
+    /// Amount returned to the depositor (tenant)
+    pub depositor_amount: i128,
+    /// Fee paid to the arbiter (0 for none)
+    pub arbiter_fee: i128,
+}
+
+/// Final outcome of a settled escrow.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Settlement {
+    /// How the escrowed amount was divided
+    pub split: ReleaseSplit,
+    /// Whether the payout came from approvals or an arbiter ruling
+    pub path: ResolutionPath,
+    /// Timestamp of the payout
+    pub settled_at: u64,
+}
+
+/// A party allowed to approve a release, with its voting weight.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Signer {
+    /// Address of the signer
+    pub address: Address,
+    /// Weight its approval contributes toward the threshold (1 for a plain vote)
+    pub weight: u32,
+}
+
+/// Signers of an escrow and the approval weight needed to release it.
+/// Escrows without an explicit set use depositor, beneficiary and arbiter at weight 1
+/// with a threshold of 2.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct SignerSet {
+    /// Parties allowed to approve a release
+    pub signers: Vec<Signer>,
+    /// Total weight that must approve the same split
+    pub threshold: u32,
+}
+
+/// Approval weight gathered for a split versus the weight required.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ApprovalProgress {
+    /// Sum of weights of unique signers approving the split
+    pub weight: u32,
+    /// Weight required to release
+    pub threshold: u32,
 }
 
 /// Records approval of fund release by a single party.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
 pub struct ReleaseApproval {
     /// Address of the party approving release
     pub signer: Address,
-    /// Target address for funds release (beneficiary or depositor)
-    pub release_to: Address,
+    /// Split of funds the signer agrees to
+    pub split: ReleaseSplit,
     /// Timestamp of the approval
     pub timestamp: u64,
 }
 
+/// Yield vault an escrow places its funded amount in, and how the accrued yield
+/// is shared out on settlement. The shares add up to 10_000 basis points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct YieldPolicy {
+    /// Vault contract implementing the `Vault` interface for the escrow token
+    pub vault: Address,
+    /// Share of the yield paid to the depositor (tenant)
+    pub tenant_bps: u32,
+    /// Share of the yield paid to the beneficiary (landlord)
+    pub landlord_bps: u32,
+    /// Share of the yield paid to the platform (contract admin)
+    pub platform_bps: u32,
+}
+
+/// Funds an escrow currently has in its yield vault.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct VaultPosition {
+    /// Amount placed in the vault
+    pub principal: i128,
+    /// Vault shares received for it
+    pub shares: i128,
+}
+
+/// Settlement split proposed by one primary party during a dispute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct SettlementOffer {
+    /// Party that proposed the split
+    pub proposer: Address,
+    /// Proposed division of the escrowed amount
+    pub split: ReleaseSplit,
+    /// Timestamp of the proposal
+    pub proposed_at: u64,
+}
+
+/// A panelist's vote on how to split a disputed escrow.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct PanelVote {
+    /// Panelist casting the vote
+    pub panelist: Address,
+    /// Split the panelist votes for
+    pub split: ReleaseSplit,
+    /// Timestamp of the vote
+    pub voted_at: u64,
+}
+
+/// Arbiters drawn from the pool to decide a dispute by majority vote.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Panel {
+    /// Current panelists
+    pub members: Vec<Address>,
+    /// Votes cast so far, one per panelist
+    pub votes: Vec<PanelVote>,
+    /// Number of draws made for this dispute; mixed into the PRNG seed
+    pub draws: u32,
+}
+
+/// Arbiter ruling that only takes effect once its appeal window closes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ProvisionalRuling {
+    /// Split the arbiter ruled
+    pub split: ReleaseSplit,
+    /// Who made the ruling (Arbiter or Panel); used when the ruling is finalized
+    pub path: ResolutionPath,
+    /// Timestamp of the ruling
+    pub ruled_at: u64,
+    /// Last timestamp at which either primary party may appeal
+    pub appeal_deadline: u64,
+}
+
+/// Appeal of a provisional ruling to the escrow's appeal arbiter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Appeal {
+    /// Primary party that appealed
+    pub appellant: Address,
+    /// Bond posted in the escrow token; returned if the appeal succeeds
+    pub bond: i128,
+    /// Timestamp of the appeal
+    pub filed_at: u64,
+    /// Last timestamp at which the appeal arbiter may decide; after it the
+    /// provisional ruling can be finalized and the bond is returned
+    pub decide_by: u64,
+}
+
+/// Contract-wide escrow settings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct EscrowConfig {
+    /// Seconds the counter-party has to respond to a new dispute
+    pub dispute_response_window: u64,
+    /// Seconds the arbiter has to rule once the response window closes
+    pub dispute_ruling_window: u64,
+    /// Seconds a provisional ruling stays open to appeal
+    pub appeal_window: u64,
+    /// Appeal bond in basis points of the disputed amount
+    pub appeal_bond_bps: u32,
+}
+
+/// Settings for the bonded arbiter registry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ArbiterRegistryConfig {
+    /// Token arbiters stake their bond in
+    pub bond_token: Address,
+    /// Minimum bond to register and stay active
+    pub min_bond: i128,
+    /// Arbiter fee per ruling in basis points of the disputed amount
+    pub fee_bps: u32,
+}
+
+/// Registry entry of a bonded arbiter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ArbiterProfile {
+    /// Bond currently staked
+    pub bond: i128,
+    /// Whether the arbiter can be named on new escrows
+    pub active: bool,
+    /// Timestamp of registration
+    pub registered_at: u64,
+    /// Timestamp from which the bond can be withdrawn after unregistering; 0 while registered
+    pub unbonds_at: u64,
+}
+
+/// Ruling record of an arbiter, kept after it leaves the registry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[contracttype]
+pub struct ArbiterStats {
+    /// Number of dispute rulings made as escrow arbiter
+    pub rulings: u32,
+    /// Number of those rulings that were appealed and decided
+    pub appeals_decided: u32,
+    /// Number of appealed rulings the appeal arbiter changed
+    pub appeals_reversed: u32,
+}
+
+/// Admin and configuration set at initialization.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ContractState {
+    pub admin: Address,
+    pub config: EscrowConfig,
+    pub initialized: bool,
+}
+
 /// Storage key variants for persistent storage.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
@@ -61,8 +448,76 @@ pub enum DataKey {
     Escrow(BytesN<32>),
     /// Store approvals for an escrow: DataKey::Approvals(escrow_id)
     Approvals(BytesN<32>),
-    /// Store dispute info: DataKey::DisputeInfo(escrow_id)
+    /// Store dispute record: DataKey::DisputeInfo(escrow_id)
     DisputeInfo(BytesN<32>),
+    /// Store deduction claim: DataKey::DeductionClaim(escrow_id)
+    DeductionClaim(BytesN<32>),
     /// Counter for total escrows created
     EscrowCount,
+    /// Final payout record: DataKey::Settlement(escrow_id)
+    Settlement(BytesN<32>),
+    /// Uncontested part of a deduction claim paid out before the dispute:
+    /// DataKey::PartialRelease(escrow_id)
+    PartialRelease(BytesN<32>),
+    /// Number of escrows a party takes part in: DataKey::PartyEscrowCount(address)
+    PartyEscrowCount(Address),
+    /// Escrow a party takes part in, by creation: DataKey::PartyEscrow(address, position)
+    PartyEscrow(Address, u32),
+    /// Number of escrows currently in a status: DataKey::StatusEscrowCount(status)
+    StatusEscrowCount(EscrowStatus),
+    /// Escrow currently in a status: DataKey::StatusEscrow(status, position)
+    StatusEscrow(EscrowStatus, u32),
+    /// Position of an escrow in its status index: DataKey::StatusPosition(escrow_id)
+    StatusPosition(BytesN<32>),
+    /// Escrow ID for a rent agreement: DataKey::AgreementEscrow(agreement_id)
+    AgreementEscrow(String),
+    /// Sum of funded escrow amounts held per token: DataKey::TotalHeld(token)
+    TotalHeld(Address),
+    /// Contract admin and configuration
+    State,
+    /// Ordered milestones of a milestone escrow: DataKey::Milestones(escrow_id)
+    Milestones(BytesN<32>),
+    /// Number of settled milestones: DataKey::MilestonesSettled(escrow_id)
+    MilestonesSettled(BytesN<32>),
+    /// Payout of one milestone: DataKey::MilestoneSettlement(escrow_id, index)
+    MilestoneSettlement(BytesN<32>, u32),
+    /// Installment schedule of a Pending escrow: DataKey::Installments(escrow_id)
+    Installments(BytesN<32>),
+    /// Open amount adjustment request: DataKey::Adjustment(escrow_id)
+    Adjustment(BytesN<32>),
+    /// Yield vault and sharing policy: DataKey::YieldPolicy(escrow_id)
+    YieldPolicy(BytesN<32>),
+    /// Funds placed in the yield vault: DataKey::VaultPosition(escrow_id)
+    VaultPosition(BytesN<32>),
+    /// Principal lost in the yield vault: DataKey::VaultLoss(escrow_id)
+    VaultLoss(BytesN<32>),
+    /// Explicit signer set: DataKey::Signers(escrow_id)
+    Signers(BytesN<32>),
+    /// Seconds an approval stays live: DataKey::ApprovalTtl(escrow_id)
+    ApprovalTtl(BytesN<32>),
+    /// Time after which the deposit can be refunded: DataKey::ReleaseAfter(escrow_id)
+    ReleaseAfter(BytesN<32>),
+    /// Second-tier arbiter for appeals: DataKey::AppealArbiter(escrow_id)
+    AppealArbiter(BytesN<32>),
+    /// Provisional arbiter ruling: DataKey::Ruling(escrow_id)
+    Ruling(BytesN<32>),
+    /// Appeal against the provisional ruling: DataKey::Appeal(escrow_id)
+    Appeal(BytesN<32>),
+    /// Open settlement proposal: DataKey::SettlementOffer(escrow_id)
+    SettlementOffer(BytesN<32>),
+    /// Arbiters eligible to sit on dispute panels
+    ArbiterPool,
+    /// Bond token, minimum bond and fee of the arbiter registry
+    RegistryConfig,
+    /// Registered arbiter profile: DataKey::Arbiter(address)
+    Arbiter(Address),
+    /// Ruling statistics of an arbiter: DataKey::ArbiterStats(address)
+    ArbiterStats(Address),
+    /// Number of open disputes an arbiter decides, alone or on a panel:
+    /// DataKey::ArbiterAssignments(address)
+    ArbiterAssignments(Address),
+    /// Requested panel size: DataKey::PanelSize(escrow_id)
+    PanelSize(BytesN<32>),
+    /// Panel assigned to the current dispute: DataKey::Panel(escrow_id)
+    Panel(BytesN<32>),
 }
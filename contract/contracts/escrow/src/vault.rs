@@ -0,0 +1,203 @@
+//! Yield vaults for the Escrow contract.
+//! An escrow can opt to place its funded amount in a vault contract while it waits
+//! for settlement. The vault is reached through the small `Vault` interface below,
+//! so any contract exposing deposit/withdraw/balance can be plugged in. On settlement
+//! everything is withdrawn and the yield is shared out according to the escrow's
+//! `YieldPolicy`. If the vault returns less than was placed, the escrow settles on
+//! what came back and the loss is recorded.
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{contractclient, vec, Address, BytesN, Env, IntoVal, Symbol};
+
+use crate::access::AccessControl;
+use crate::custody::Custody;
+use crate::errors::EscrowError;
+use crate::storage::EscrowStorage;
+use crate::types::{Escrow, EscrowStatus, VaultPosition, YieldPolicy};
+
+/// Interface a yield vault must implement. A vault holds a single token.
+#[contractclient(name = "VaultClient")]
+pub trait Vault {
+    /// Pull `amount` of the vault token from `from` and return the shares minted to it.
+    fn deposit(env: Env, from: Address, amount: i128) -> i128;
+
+    /// Burn `shares` owned by `to` and send it their current value, which is returned.
+    fn withdraw(env: Env, to: Address, shares: i128) -> i128;
+
+    /// Current value of all shares owned by `owner`, in the vault token.
+    fn balance(env: Env, owner: Address) -> i128;
+}
+
+/// Yield vault placement, withdrawal and yield sharing.
+pub struct YieldVault;
+
+impl YieldVault {
+    /// Opt a Pending escrow into a yield vault; its funds are placed there once funded.
+    /// The vault holds the depositor's money, so the depositor must agree to it.
+    ///
+    /// CHECKS:
+    /// - Caller must authorize and be the beneficiary
+    /// - Depositor must authorize
+    /// - Escrow must be in Pending state and not released in milestones
+    /// - Policy shares must add up to 10_000 basis points
+    ///
+    /// EFFECTS:
+    /// - Store the yield policy
+    pub fn set_yield_policy(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        caller: &Address,
+        policy: YieldPolicy,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Caller must authorize
+        caller.require_auth();
+
+        let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_beneficiary(&escrow, caller)?;
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Pending
+            || EscrowStorage::get_milestones(env, escrow_id).is_some()
+        {
+            return Err(EscrowError::InvalidState);
+        }
+
+        let total_bps =
+            policy.tenant_bps as u64 + policy.landlord_bps as u64 + policy.platform_bps as u64;
+        if total_bps != 10_000 {
+            return Err(EscrowError::InvalidConfig);
+        }
+
+        // EFFECTS: Store the policy
+        EscrowStorage::save_yield_policy(env, escrow_id, &policy);
+
+        Ok(())
+    }
+
+    /// Move a freshly funded escrow's amount into its vault, if it has a yield policy.
+    /// The amount leaves the contract's custody total until it is withdrawn.
+    pub fn place(env: &Env, escrow: &Escrow) -> Result<(), EscrowError> {
+        let Some(policy) = EscrowStorage::get_yield_policy(env, &escrow.id) else {
+            return Ok(());
+        };
+
+        // EFFECTS: Funds leave custody
+        EscrowStorage::sub_total_held(env, &escrow.token, escrow.funded);
+
+        // INTERACTIONS: Let the vault pull the funds, then record the shares
+        let contract = env.current_contract_address();
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: escrow.token.clone(),
+                    fn_name: Symbol::new(env, "transfer"),
+                    args: (contract.clone(), policy.vault.clone(), escrow.funded).into_val(env),
+                },
+                sub_invocations: vec![env],
+            }),
+        ]);
+        let shares = VaultClient::new(env, &policy.vault).deposit(&contract, &escrow.funded);
+
+        let position = VaultPosition {
+            principal: escrow.funded,
+            shares,
+        };
+        EscrowStorage::save_vault_position(env, &escrow.id, &position);
+
+        Ok(())
+    }
+
+    /// Withdraw an escrow's funds from its vault, if any are there, and share out the
+    /// yield: tenant and landlord shares by policy, the remainder to the platform.
+    /// Returns the loss when the vault gives back less than the principal; the caller
+    /// settles on what came back (see `share_loss`).
+    ///
+    /// EFFECTS:
+    /// - Clear the position; what the vault returns goes back to custody
+    /// - Record any loss against the escrow
+    ///
+    /// INTERACTIONS:
+    /// - Withdraw every share from the vault
+    /// - Pay each non-zero yield share to depositor, beneficiary and admin
+    pub fn recall(env: &Env, escrow: &Escrow) -> Result<i128, EscrowError> {
+        let Some(position) = EscrowStorage::get_vault_position(env, &escrow.id) else {
+            return Ok(0);
+        };
+        let policy =
+            EscrowStorage::get_yield_policy(env, &escrow.id).ok_or(EscrowError::InvalidState)?;
+        let state = EscrowStorage::get_state(env).ok_or(EscrowError::NotInitialized)?;
+
+        // EFFECTS: Clear the position before calling out
+        EscrowStorage::clear_vault_position(env, &escrow.id);
+
+        // INTERACTIONS: Withdraw, then share out the yield
+        let contract = env.current_contract_address();
+        let returned = VaultClient::new(env, &policy.vault).withdraw(&contract, &position.shares);
+        if returned < 0 {
+            return Err(EscrowError::VaultShortfall);
+        }
+        EscrowStorage::add_total_held(env, &escrow.token, returned);
+
+        if returned < position.principal {
+            let loss = position.principal - returned;
+            EscrowStorage::save_vault_loss(env, &escrow.id, loss);
+            return Ok(loss);
+        }
+
+        let accrued = returned - position.principal;
+        let tenant_share = accrued * policy.tenant_bps as i128 / 10_000;
+        let landlord_share = accrued * policy.landlord_bps as i128 / 10_000;
+        let payouts = [
+            (&escrow.depositor, tenant_share),
+            (&escrow.beneficiary, landlord_share),
+            (&state.admin, accrued - tenant_share - landlord_share),
+        ];
+        for (recipient, amount) in payouts {
+            if amount > 0 {
+                Custody::pay_out(env, &escrow.token, recipient, amount)?;
+            }
+        }
+
+        Ok(0)
+    }
+
+    /// Scale `parts`, which add up to `total`, down pro rata to what is left after a
+    /// vault `loss`. The last part takes the rounding.
+    pub fn share_loss(parts: &mut [i128], total: i128, loss: i128) {
+        if loss == 0 || total == 0 {
+            return;
+        }
+        let left = total - loss;
+        let last = parts.len() - 1;
+        let mut scaled: i128 = 0;
+        for part in parts[..last].iter_mut() {
+            *part = *part * left / total;
+            scaled += *part;
+        }
+        parts[last] = left - scaled;
+    }
+
+    /// Get the yield policy of an escrow, if it has one.
+    pub fn get_yield_policy(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+    ) -> Result<Option<YieldPolicy>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_yield_policy(env, escrow_id))
+    }
+
+    /// Get the principal an escrow lost in its vault (0 if none).
+    pub fn get_vault_loss(env: &Env, escrow_id: &BytesN<32>) -> Result<i128, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_vault_loss(env, escrow_id))
+    }
+
+    /// Get the funds an escrow currently has in its vault, if any.
+    pub fn get_vault_position(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+    ) -> Result<Option<VaultPosition>, EscrowError> {
+        EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_vault_position(env, escrow_id))
+    }
+}
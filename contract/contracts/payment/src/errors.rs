@@ -5,6 +5,10 @@ use soroban_sdk::contracterror;
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
 pub enum PaymentError {
+    /// Contract is already initialized
+    AlreadyInitialized = 1,
+    /// Configuration values are invalid
+    InvalidConfig = 3,
     /// Payment record not found
     PaymentNotFound = 11,
     /// Payment processing failed
@@ -21,4 +25,16 @@ pub enum PaymentError {
     PaymentNotDue = 18,
     /// Invalid amount provided
     InvalidAmount = 5,
+    /// Contract state is missing (not initialized)
+    InvalidState = 15,
+    /// Payments are paused
+    ContractPaused = 19,
+    /// Token is not on the allowed list
+    TokenNotAllowed = 20,
+    /// Agreement billing cycle has a zero-length period
+    InvalidBillingCycle = 21,
+    /// Legacy payments must be migrated before new ones are accepted
+    MigrationPending = 22,
+    /// Every period up to the agreement's end date has been paid
+    AgreementEnded = 23,
 }
@@ -0,0 +1,91 @@
+//! Contract events for the Payment contract.
+use soroban_sdk::{contractevent, Address, Env, String, Vec};
+
+use crate::types::{Config, PaymentRecord};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractInitialized {
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigUpdated {
+    pub old_fee_bps: u32,
+    pub new_fee_bps: u32,
+    pub old_fee_collector: Address,
+    pub new_fee_collector: Address,
+    pub old_allowed_tokens: Vec<Address>,
+    pub new_allowed_tokens: Vec<Address>,
+    pub old_paused: bool,
+    pub new_paused: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeCollectorUpdated {
+    pub old_collector: Address,
+    pub new_collector: Address,
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RentPaid {
+    #[topic]
+    pub agreement_id: String,
+    pub payment_number: u32,
+    pub tenant: Address,
+    pub amount: i128,
+    pub landlord_amount: i128,
+    pub agent_amount: i128,
+    pub platform_amount: i128,
+    pub paid_at: u64,
+}
+
+pub(crate) fn contract_initialized(env: &Env, admin: Address) {
+    ContractInitialized { admin }.publish(env);
+}
+
+pub(crate) fn config_updated(env: &Env, old_config: Config, new_config: Config) {
+    ConfigUpdated {
+        old_fee_bps: old_config.fee_bps,
+        new_fee_bps: new_config.fee_bps,
+        old_fee_collector: old_config.fee_collector,
+        new_fee_collector: new_config.fee_collector,
+        old_allowed_tokens: old_config.allowed_tokens,
+        new_allowed_tokens: new_config.allowed_tokens,
+        old_paused: old_config.paused,
+        new_paused: new_config.paused,
+    }
+    .publish(env);
+}
+
+pub(crate) fn fee_collector_updated(
+    env: &Env,
+    old_collector: Address,
+    new_collector: Address,
+    admin: Address,
+) {
+    FeeCollectorUpdated {
+        old_collector,
+        new_collector,
+        admin,
+    }
+    .publish(env);
+}
+
+pub(crate) fn rent_paid(env: &Env, record: &PaymentRecord) {
+    RentPaid {
+        agreement_id: record.agreement_id.clone(),
+        payment_number: record.payment_number,
+        tenant: record.tenant.clone(),
+        amount: record.amount,
+        landlord_amount: record.landlord_amount,
+        agent_amount: record.agent_amount,
+        platform_amount: record.platform_amount,
+        paid_at: record.timestamp,
+    }
+    .publish(env);
+}
@@ -6,10 +6,12 @@
 //! Handles rent payment processing with automatic commission splitting
 //! and payment record management.
 
-use soroban_sdk::{contract, contractimpl, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
 
 pub mod errors;
+pub mod events;
 pub mod payment_impl;
+pub mod schedule;
 pub mod storage;
 pub mod types;
 
@@ -18,11 +20,20 @@ mod tests;
 
 // Re-export public APIs
 pub use errors::PaymentError;
-pub use payment_impl::{calculate_payment_split, create_payment_record};
+pub use payment_impl::{
+    calculate_payment_split, calculate_rent_split, create_payment_record, MAX_PAGE_SIZE,
+};
 pub use storage::DataKey;
-pub use types::{PaymentRecord, PaymentSplit};
+pub use types::{
+    BillingCycle, Config, ContractState, LegacyPaymentSplit, LegacyRentAgreement, PaymentPage,
+    PaymentRecord, PaymentSplit, ScheduledPayment,
+};
 
 use crate::errors::PaymentError as Error;
+use crate::payment_impl::{
+    agreement_payment_count, agreement_total_paid, append_payment_record, migrate_agreement,
+    payment_id, payment_page, require_payments_migrated, save_payment_split,
+};
 use crate::storage::DataKey as StorageKey;
 use crate::types::{AgreementStatus, RentAgreement};
 
@@ -31,20 +42,83 @@ pub struct PaymentContract;
 
 #[contractimpl]
 impl PaymentContract {
-    /// Sets the platform fee collector address
-    pub fn set_platform_fee_collector(env: Env, collector: Address) {
-        collector.require_auth();
-        env.storage()
-            .instance()
-            .set(&StorageKey::PlatformFeeCollector, &collector);
+    /// Initialize the contract with an admin and configuration.
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - If the contract has already been initialized
+    /// * `InvalidConfig` - If configuration values are invalid
+    pub fn initialize(env: Env, admin: Address, config: Config) -> Result<(), Error> {
+        if env.storage().instance().has(&StorageKey::State) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+
+        if config.fee_bps > 10_000 {
+            return Err(Error::InvalidConfig);
+        }
+
+        let state = ContractState {
+            admin: admin.clone(),
+            config,
+            initialized: true,
+        };
+
+        env.storage().instance().set(&StorageKey::State, &state);
+        env.storage().instance().extend_ttl(500000, 500000);
+
+        events::contract_initialized(&env, admin);
+
+        Ok(())
     }
 
-    /// Get a payment record by ID
-    pub fn get_payment(env: Env, payment_id: String) -> Result<PaymentRecord, Error> {
-        env.storage()
-            .persistent()
-            .get(&StorageKey::Payment(payment_id))
-            .ok_or(Error::PaymentNotFound)
+    pub fn get_state(env: Env) -> Option<ContractState> {
+        env.storage().instance().get(&StorageKey::State)
+    }
+
+    /// Update contract configuration.
+    ///
+    /// # Errors
+    /// * `InvalidState` - If contract state is missing
+    /// * `InvalidConfig` - If configuration values are invalid
+    pub fn update_config(env: Env, new_config: Config) -> Result<(), Error> {
+        let mut state = Self::get_state(env.clone()).ok_or(Error::InvalidState)?;
+
+        state.admin.require_auth();
+
+        if new_config.fee_bps > 10_000 {
+            return Err(Error::InvalidConfig);
+        }
+
+        let old_config = state.config.clone();
+        state.config = new_config.clone();
+
+        env.storage().instance().set(&StorageKey::State, &state);
+        env.storage().instance().extend_ttl(500000, 500000);
+
+        events::config_updated(&env, old_config, new_config);
+
+        Ok(())
+    }
+
+    /// Sets the platform fee collector address. Requires admin authorization.
+    ///
+    /// # Errors
+    /// * `InvalidState` - If contract state is missing
+    pub fn set_platform_fee_collector(env: Env, collector: Address) -> Result<(), Error> {
+        let mut state = Self::get_state(env.clone()).ok_or(Error::InvalidState)?;
+
+        state.admin.require_auth();
+
+        let old_collector = state.config.fee_collector.clone();
+        state.config.fee_collector = collector.clone();
+
+        env.storage().instance().set(&StorageKey::State, &state);
+        env.storage().instance().extend_ttl(500000, 500000);
+
+        events::fee_collector_updated(&env, old_collector, collector, state.admin);
+
+        Ok(())
     }
 
     /// Get total payment count
@@ -57,48 +131,90 @@ impl PaymentContract {
 
     /// Get total amount paid for a specific agreement
     pub fn get_total_paid(env: Env, agreement_id: String) -> Result<i128, Error> {
-        let payment_count: u32 = env
+        Ok(agreement_total_paid(&env, &agreement_id))
+    }
+
+    /// Get an agreement's payments, oldest first, one page at a time. Pass 0 as the
+    /// cursor for the first page, then each page's `next_cursor`. Pages hold at most
+    /// `MAX_PAGE_SIZE` records.
+    pub fn get_payment_history(
+        env: Env,
+        agreement_id: String,
+        cursor: u32,
+        limit: u32,
+    ) -> PaymentPage {
+        payment_page(&env, &agreement_id, cursor, limit)
+    }
+
+    /// Move up to the next `limit` legacy `DataKey::Payment` entries onto their
+    /// agreements' payment lists and running totals, oldest first (admin only).
+    /// Progress is kept in storage, so each call carries on where the last one
+    /// stopped. Legacy payments after the eleventh all shared one key, so only the
+    /// last of them is still there to move. `pay_rent` is refused until every legacy
+    /// payment has been moved, so migrated records always come before new ones. Returns the number of legacy
+    /// IDs processed so far; done once it reaches `get_payment_count`.
+    ///
+    /// # Errors
+    /// * `InvalidState` - If the contract has not been initialized
+    pub fn migrate_payments(env: Env, limit: u32) -> Result<u32, Error> {
+        let state = Self::get_state(env.clone()).ok_or(Error::InvalidState)?;
+        state.admin.require_auth();
+
+        let payment_count = Self::get_payment_count(env.clone());
+        let start: u32 = env
             .storage()
             .instance()
-            .get(&StorageKey::PaymentCount)
+            .get(&StorageKey::MigrationCursor)
             .unwrap_or(0);
+        let end = start.saturating_add(limit).min(payment_count).max(start);
 
-        let mut total: i128 = 0;
-
-        for i in 0..payment_count {
-            let payment_id = Self::u32_to_string(&env, i);
-            if let Some(payment) = env
+        for i in start..end {
+            let key = StorageKey::Payment(payment_id(&env, i));
+            if let Some(mut record) = env
                 .storage()
                 .persistent()
-                .get::<StorageKey, PaymentRecord>(&StorageKey::Payment(payment_id))
+                .get::<StorageKey, PaymentRecord>(&key)
             {
-                if payment.agreement_id == agreement_id {
-                    total += payment.amount;
-                }
+                append_payment_record(&env, &mut record);
+                env.storage().persistent().remove(&key);
             }
         }
 
-        Ok(total)
+        env.storage()
+            .instance()
+            .set(&StorageKey::MigrationCursor, &end);
+        if end >= payment_count {
+            env.storage()
+                .instance()
+                .set(&StorageKey::PaymentsMigrated, &true);
+        }
+
+        Ok(end)
     }
 
-    fn u32_to_string(env: &Env, num: u32) -> String {
-        match num {
-            0 => String::from_str(env, "0"),
-            1 => String::from_str(env, "1"),
-            2 => String::from_str(env, "2"),
-            3 => String::from_str(env, "3"),
-            4 => String::from_str(env, "4"),
-            5 => String::from_str(env, "5"),
-            6 => String::from_str(env, "6"),
-            7 => String::from_str(env, "7"),
-            8 => String::from_str(env, "8"),
-            9 => String::from_str(env, "9"),
-            10 => String::from_str(env, "10"),
-            _ => String::from_str(env, "unknown"),
+    /// Rewrite agreements stored in the legacy layout, which embedded the payment
+    /// history, into the current one (admin only). Each month's split moves to its
+    /// own `get_payment_split` entry and is appended to the agreement's payment list. Agreements already migrated are skipped, so the
+    /// call can be repeated safely. Returns the number of agreements rewritten.
+    ///
+    /// # Errors
+    /// * `InvalidState` - If the contract has not been initialized
+    pub fn migrate_agreements(env: Env, agreement_ids: Vec<String>) -> Result<u32, Error> {
+        let state = Self::get_state(env.clone()).ok_or(Error::InvalidState)?;
+        state.admin.require_auth();
+
+        let mut migrated = 0;
+        for agreement_id in agreement_ids.iter() {
+            if migrate_agreement(&env, &agreement_id) {
+                migrated += 1;
+            }
         }
+        Ok(migrated)
     }
 
-    /// Process rent payment with 90/10 landlord/platform split
+    /// Process a rent payment in one atomic call, split three ways: the platform fee
+    /// (config `fee_bps`), the agent commission (agreement `agent_commission_rate`,
+    /// when there is an agent) and the landlord remainder.
     /// Follows checks-effects-interactions pattern for reentrancy safety
     pub fn pay_rent(
         env: Env,
@@ -111,6 +227,14 @@ impl PaymentContract {
         // Authorization
         from.require_auth();
 
+        let config = Self::get_state(env.clone())
+            .ok_or(Error::InvalidState)?
+            .config;
+        if config.paused {
+            return Err(Error::ContractPaused);
+        }
+        require_payments_migrated(&env)?;
+
         // Load agreement
         let mut agreement: RentAgreement = env
             .storage()
@@ -131,61 +255,113 @@ impl PaymentContract {
             return Err(Error::InvalidPaymentAmount);
         }
 
+        if !config.allowed_tokens.contains(&agreement.payment_token) {
+            return Err(Error::TokenNotAllowed);
+        }
+
+        // Due dates follow the schedule, however late earlier payments were, and
+        // stop at the end date
         let current_time = env.ledger().timestamp();
-        if current_time < agreement.next_payment_due {
+        let period = agreement_payment_count(&env, &agreement_id);
+        let due_date = schedule::due_date(&agreement, period)?;
+        if due_date >= agreement.end_date {
+            return Err(Error::AgreementEnded);
+        }
+        if current_time < due_date {
             return Err(Error::PaymentNotDue);
         }
 
-        // Calculate 90/10 split
-        let landlord_amount = (payment_amount * 90) / 100;
-        let platform_amount = payment_amount - landlord_amount;
-
-        let platform_collector: Address = env
-            .storage()
-            .instance()
-            .get(&StorageKey::PlatformFeeCollector)
-            .ok_or(Error::PaymentFailed)?;
+        // Calculate platform fee, agent commission and landlord remainder
+        let (landlord_amount, agent_amount, platform_amount) =
+            calculate_rent_split(&agreement, payment_amount, config.fee_bps)?;
 
         // Effects: Update state BEFORE external calls
-        let payment_month = agreement.payment_history.len();
-        agreement.payment_history.set(
-            payment_month,
-            PaymentSplit {
-                landlord_amount,
-                platform_amount,
-                token: agreement.payment_token.clone(),
-                payment_date: current_time,
-            },
-        );
-        agreement.next_payment_due = current_time + 2_592_000; // 30 days
+        let split = PaymentSplit {
+            landlord_amount,
+            agent_amount,
+            platform_amount,
+            token: agreement.payment_token.clone(),
+            payment_date: current_time,
+        };
+        save_payment_split(&env, &agreement_id, period, &split);
+        agreement.next_payment_due = schedule::due_date(&agreement, period + 1)?;
+
+        let mut record = create_payment_record(
+            &env,
+            &agreement_id,
+            payment_amount,
+            landlord_amount,
+            agent_amount,
+            platform_amount,
+            &from,
+            0,
+            current_time,
+        )?;
 
         env.storage()
             .persistent()
-            .set(&StorageKey::Agreement(agreement_id.clone()), &agreement);
+            .set(&StorageKey::Agreement(agreement_id), &agreement);
+        append_payment_record(&env, &mut record);
+        let payment_count = Self::get_payment_count(env.clone());
+        env.storage()
+            .instance()
+            .set(&StorageKey::PaymentCount, &(payment_count + 1));
 
         // Interactions: External calls AFTER state updates
         let token_client = token::Client::new(&env, &agreement.payment_token);
         token_client.transfer(&from, &agreement.landlord, &landlord_amount);
-        token_client.transfer(&from, &platform_collector, &platform_amount);
+        if let Some(agent) = &agreement.agent {
+            if agent_amount > 0 {
+                token_client.transfer(&from, agent, &agent_amount);
+            }
+        }
+        if platform_amount > 0 {
+            token_client.transfer(&from, &config.fee_collector, &platform_amount);
+        }
+
+        events::rent_paid(&env, &record);
 
         Ok(())
     }
 
-    /// Get payment details for a specific month
-    pub fn get_payment_split(
+    /// Get the record of an agreement's `payment_number`-th payment (1-based)
+    pub fn get_payment_record(
         env: Env,
         agreement_id: String,
-        month: u32,
-    ) -> Result<PaymentSplit, Error> {
+        payment_number: u32,
+    ) -> Result<PaymentRecord, Error> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::PaymentRecord(agreement_id, payment_number))
+            .ok_or(Error::PaymentNotFound)
+    }
+
+    /// List every payment due under an agreement through its `end_date`, with due
+    /// dates anchored to `start_date` by the agreement's billing cycle.
+    ///
+    /// # Errors
+    /// * `AgreementNotFound` - If the agreement does not exist
+    /// * `InvalidBillingCycle` - If the billing cycle has a zero-length period
+    pub fn get_payment_schedule(
+        env: Env,
+        agreement_id: String,
+    ) -> Result<Vec<ScheduledPayment>, Error> {
         let agreement: RentAgreement = env
             .storage()
             .persistent()
-            .get(&StorageKey::Agreement(agreement_id))
+            .get(&StorageKey::Agreement(agreement_id.clone()))
             .ok_or(Error::AgreementNotFound)?;
 
-        agreement
-            .payment_history
-            .get(month)
-            .ok_or(Error::PaymentNotFound)
+        let paid = agreement_payment_count(&env, &agreement_id);
+        schedule::payment_schedule(&env, &agreement, paid)
+    }
+
+    /// Get payment details for a specific month (0-based)
+    pub fn get_payment_split(
+        env: Env,
+        agreement_id: String,
+        month: u32,
+    ) -> Result<PaymentSplit, Error> {
+        payment_impl::get_payment_split(&env, &agreement_id, month).ok_or(Error::PaymentNotFound)
     }
 }
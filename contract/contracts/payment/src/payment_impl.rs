@@ -1,9 +1,15 @@
 //! Payment processing implementation.
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{Address, Env, Map, String, Symbol, Val, Vec};
 
 use crate::errors::PaymentError;
-use crate::storage::DataKey;
-use crate::types::{AgreementStatus, PaymentRecord, RentAgreement};
+use crate::schedule;
+use crate::storage::{DataKey, PAYMENT_SPLIT_TTL_EXTEND_TO, PAYMENT_SPLIT_TTL_THRESHOLD};
+use crate::types::{
+    BillingCycle, LegacyRentAgreement, PaymentPage, PaymentRecord, PaymentSplit, RentAgreement,
+};
+
+/// Largest page `get_payment_history` returns; bigger limits are capped.
+pub const MAX_PAGE_SIZE: u32 = 50;
 
 /// Create an immutable payment record
 pub fn create_payment_record(
@@ -12,6 +18,7 @@ pub fn create_payment_record(
     amount: i128,
     landlord_amount: i128,
     agent_amount: i128,
+    platform_amount: i128,
     tenant: &Address,
     payment_number: u32,
     timestamp: u64,
@@ -22,6 +29,7 @@ pub fn create_payment_record(
         amount,
         landlord_amount,
         agent_amount,
+        platform_amount,
         timestamp,
         tenant: tenant.clone(),
     })
@@ -35,88 +43,194 @@ pub fn calculate_payment_split(amount: &i128, commission_rate: &u32) -> (i128, i
     (landlord_amount, agent_amount)
 }
 
-/// Process rent payment with automatic commission splitting
-/// This is the alternate implementation used by RentalContract
-#[allow(deprecated)]
-#[allow(dead_code)]
-pub fn pay_rent_with_agent(
-    env: Env,
-    agreement_id: String,
-    token: Address,
+/// Split a rent payment three ways: the platform fee (`fee_bps` of the amount), the
+/// agent commission (via `calculate_payment_split`, only when the agreement has an
+/// agent) and the landlord remainder. Returns (landlord, agent, platform).
+pub fn calculate_rent_split(
+    agreement: &RentAgreement,
     amount: i128,
-) -> Result<(), PaymentError> {
-    use soroban_sdk::token::Client as TokenClient;
+    fee_bps: u32,
+) -> Result<(i128, i128, i128), PaymentError> {
+    let platform_amount = (amount * fee_bps as i128) / 10000;
+    let agent_amount = match agreement.agent {
+        Some(_) => calculate_payment_split(&amount, &agreement.agent_commission_rate).1,
+        None => 0,
+    };
+    let landlord_amount = amount - platform_amount - agent_amount;
+    if landlord_amount < 0 {
+        return Err(PaymentError::InvalidAmount);
+    }
+    Ok((landlord_amount, agent_amount, platform_amount))
+}
 
-    // Load agreement
-    let mut agreement: RentAgreement = env
-        .storage()
+/// Number of payments recorded for an agreement
+pub fn agreement_payment_count(env: &Env, agreement_id: &String) -> u32 {
+    env.storage()
         .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(PaymentError::InvalidAmount)?;
+        .get(&DataKey::AgreementPaymentCount(agreement_id.clone()))
+        .unwrap_or(0)
+}
 
-    // Validate agreement is active
-    if agreement.status != AgreementStatus::Active {
-        return Err(PaymentError::AgreementNotActive);
-    }
+/// Running total paid towards an agreement
+pub fn agreement_total_paid(env: &Env, agreement_id: &String) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgreementTotalPaid(agreement_id.clone()))
+        .unwrap_or(0)
+}
 
-    // Validate amount matches monthly rent exactly
-    if amount != agreement.monthly_rent {
-        return Err(PaymentError::InvalidAmount);
+/// Fail while legacy payments are still waiting to be migrated, so that new records
+/// are never appended ahead of older ones. A contract without any payments has
+/// nothing to migrate and is marked done right away.
+pub fn require_payments_migrated(env: &Env) -> Result<(), PaymentError> {
+    let storage = env.storage().instance();
+    if storage.get(&DataKey::PaymentsMigrated).unwrap_or(false) {
+        return Ok(());
     }
+    if storage.get(&DataKey::PaymentCount).unwrap_or(0u32) > 0 {
+        return Err(PaymentError::MigrationPending);
+    }
+    storage.set(&DataKey::PaymentsMigrated, &true);
+    Ok(())
+}
 
-    // Authorize tenant
-    agreement.tenant.require_auth();
-
-    // Calculate payment split
-    let (landlord_amount, agent_amount) =
-        calculate_payment_split(&amount, &agreement.agent_commission_rate);
-
-    // Execute atomic token transfers
-    let token_client = TokenClient::new(&env, &token);
+/// Append a record to its agreement's payment list and add it to the running total.
+/// The record is renumbered to its position in the list, which is returned.
+pub fn append_payment_record(env: &Env, record: &mut PaymentRecord) -> u32 {
+    let agreement_id = record.agreement_id.clone();
+    let payment_number = agreement_payment_count(env, &agreement_id) + 1;
+    let total = agreement_total_paid(env, &agreement_id) + record.amount;
+    record.payment_number = payment_number;
+
+    let storage = env.storage().persistent();
+    storage.set(
+        &DataKey::PaymentRecord(agreement_id.clone(), payment_number),
+        record,
+    );
+    storage.set(
+        &DataKey::AgreementPaymentCount(agreement_id.clone()),
+        &payment_number,
+    );
+    storage.set(&DataKey::AgreementTotalPaid(agreement_id), &total);
 
-    // Transfer to landlord
-    token_client.transfer(&agreement.tenant, &agreement.landlord, &landlord_amount);
+    payment_number
+}
 
-    // Transfer to agent if present
-    if let Some(agent_address) = &agreement.agent {
-        if agent_amount > 0 {
-            token_client.transfer(&agreement.tenant, agent_address, &agent_amount);
+/// Load up to `limit` of an agreement's payments starting at position `cursor`
+/// (0 for the first page).
+pub fn payment_page(env: &Env, agreement_id: &String, cursor: u32, limit: u32) -> PaymentPage {
+    let count = agreement_payment_count(env, agreement_id);
+    let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+    let mut records = Vec::new(env);
+    for position in cursor..end {
+        if let Some(record) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PaymentRecord(agreement_id.clone(), position + 1))
+        {
+            records.push_back(record);
         }
     }
 
-    // Create payment record
-    let timestamp = env.ledger().timestamp();
-    let payment_record = create_payment_record(
-        &env,
-        &agreement_id,
-        amount,
-        landlord_amount,
-        agent_amount,
-        &agreement.tenant,
-        agreement.payment_count + 1,
-        timestamp,
-    )?;
+    PaymentPage {
+        records,
+        next_cursor: if end < count { Some(end) } else { None },
+    }
+}
+
+/// Key of the `num`-th legacy `DataKey::Payment` entry. Legacy IDs were spelled out
+/// only up to 10; every later payment was stored under "unknown", overwriting the
+/// one before it.
+pub fn payment_id(env: &Env, num: u32) -> String {
+    const IDS: [&str; 11] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10"];
+    String::from_str(env, IDS.get(num as usize).copied().unwrap_or("unknown"))
+}
 
-    // Update agreement totals
-    agreement.total_rent_paid += amount;
-    agreement.payment_count += 1;
+/// Store the split of an agreement's payment for `month` in its own entry
+pub fn save_payment_split(env: &Env, agreement_id: &String, month: u32, split: &PaymentSplit) {
+    let key = DataKey::PaymentSplit(agreement_id.clone(), month);
+    let storage = env.storage().persistent();
+    storage.set(&key, split);
+    storage.extend_ttl(
+        &key,
+        PAYMENT_SPLIT_TTL_THRESHOLD,
+        PAYMENT_SPLIT_TTL_EXTEND_TO,
+    );
+}
 
-    // Persist updated agreement
+/// Load the split of an agreement's payment for `month`, if it was paid
+pub fn get_payment_split(env: &Env, agreement_id: &String, month: u32) -> Option<PaymentSplit> {
     env.storage()
         .persistent()
-        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
-
-    // Persist payment record
-    env.storage().persistent().set(
-        &DataKey::PaymentRecord(agreement_id.clone(), agreement.payment_count),
-        &payment_record,
-    );
+        .get(&DataKey::PaymentSplit(agreement_id.clone(), month))
+}
 
-    // Emit event
-    env.events().publish(
-        (String::from_str(&env, "rent_paid"), agreement_id),
-        (amount, landlord_amount, agent_amount, timestamp),
-    );
+/// Rewrite an agreement stored in the legacy layout, which embedded its payment
+/// history, into the current one. Each month's split moves to its own entry and is
+/// appended to the agreement's payment list, which the legacy `pay_rent` did not
+/// keep, and the agreement is billed by calendar month from its start date.
+/// Returns whether the agreement was rewritten; agreements already in the current
+/// layout, or missing, are left alone.
+pub fn migrate_agreement(env: &Env, agreement_id: &String) -> bool {
+    let key = DataKey::Agreement(agreement_id.clone());
+    let Some(fields) = env.storage().persistent().get::<_, Map<Symbol, Val>>(&key) else {
+        return false;
+    };
+    if !fields.contains_key(Symbol::new(env, "payment_history")) {
+        return false;
+    }
+    let Some(legacy) = env
+        .storage()
+        .persistent()
+        .get::<_, LegacyRentAgreement>(&key)
+    else {
+        return false;
+    };
+
+    for (month, split) in legacy.payment_history.iter() {
+        let mut record = PaymentRecord {
+            agreement_id: agreement_id.clone(),
+            payment_number: 0,
+            amount: split.landlord_amount + split.platform_amount,
+            landlord_amount: split.landlord_amount,
+            agent_amount: 0,
+            platform_amount: split.platform_amount,
+            timestamp: split.payment_date,
+            tenant: legacy.tenant.clone(),
+        };
+        append_payment_record(env, &mut record);
+        let split = PaymentSplit {
+            landlord_amount: split.landlord_amount,
+            agent_amount: 0,
+            platform_amount: split.platform_amount,
+            token: split.token,
+            payment_date: split.payment_date,
+        };
+        save_payment_split(env, agreement_id, month, &split);
+    }
 
-    Ok(())
+    let mut agreement = RentAgreement {
+        agreement_id: legacy.agreement_id,
+        landlord: legacy.landlord,
+        tenant: legacy.tenant,
+        agent: legacy.agent,
+        monthly_rent: legacy.monthly_rent,
+        security_deposit: legacy.security_deposit,
+        start_date: legacy.start_date,
+        end_date: legacy.end_date,
+        agent_commission_rate: legacy.agent_commission_rate,
+        status: legacy.status,
+        total_rent_paid: legacy.total_rent_paid,
+        payment_count: legacy.payment_count,
+        signed_at: legacy.signed_at,
+        payment_token: legacy.payment_token,
+        next_payment_due: legacy.next_payment_due,
+        billing_cycle: BillingCycle::CalendarMonth,
+    };
+    agreement.next_payment_due =
+        schedule::due_date(&agreement, agreement_payment_count(env, agreement_id))
+            .unwrap_or(legacy.next_payment_due);
+    env.storage().persistent().set(&key, &agreement);
+    true
 }
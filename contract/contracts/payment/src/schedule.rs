@@ -0,0 +1,103 @@
+//! Payment schedule for rent agreements.
+//! Due dates are anchored to the agreement's `start_date` and computed from the
+//! period index, so a late payment never moves later due dates.
+use soroban_sdk::{Env, Vec};
+
+use crate::errors::PaymentError;
+use crate::types::{BillingCycle, RentAgreement, ScheduledPayment};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Due date of the agreement's payment for `period` (0 for the first payment)
+pub fn due_date(agreement: &RentAgreement, period: u32) -> Result<u64, PaymentError> {
+    match agreement.billing_cycle {
+        BillingCycle::CalendarMonth => Ok(add_months(agreement.start_date, period)),
+        BillingCycle::FixedPeriod(0) => Err(PaymentError::InvalidBillingCycle),
+        BillingCycle::FixedPeriod(length) => Ok(agreement
+            .start_date
+            .saturating_add(length.saturating_mul(period as u64))),
+    }
+}
+
+/// Every payment due from `start_date` up to (not including) `end_date`, the first
+/// `paid` of them marked as paid
+pub fn payment_schedule(
+    env: &Env,
+    agreement: &RentAgreement,
+    paid: u32,
+) -> Result<Vec<ScheduledPayment>, PaymentError> {
+    let mut schedule = Vec::new(env);
+    let mut period = 0;
+    loop {
+        let due = due_date(agreement, period)?;
+        if due >= agreement.end_date {
+            break;
+        }
+        schedule.push_back(ScheduledPayment {
+            period,
+            due_date: due,
+            amount: agreement.monthly_rent,
+            paid: period < paid,
+        });
+        period += 1;
+    }
+    Ok(schedule)
+}
+
+/// `timestamp` moved forward `months` calendar months, keeping the time of day and
+/// the day of month, clamped to the last day of a shorter month
+fn add_months(timestamp: u64, months: u32) -> u64 {
+    let days = timestamp / SECONDS_PER_DAY;
+    let time_of_day = timestamp % SECONDS_PER_DAY;
+    let (year, month, day) = civil_from_days(days as i64);
+
+    let month_index = month as i64 - 1 + months as i64;
+    let year = year + month_index / 12;
+    let month = (month_index % 12) as u32 + 1;
+    let day = day.min(days_in_month(year, month));
+
+    days_from_civil(year, month, day) as u64 * SECONDS_PER_DAY + time_of_day
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// (year, month, day) of a day count since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Day count since 1970-01-01 of a (year, month, day) date
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
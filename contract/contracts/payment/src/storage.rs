@@ -1,18 +1,36 @@
 //! Storage key definitions for the Payment contract.
 use soroban_sdk::{contracttype, String};
 
+/// Ledgers per day at a 5 second close time
+const DAY_IN_LEDGERS: u32 = 17_280;
+/// Lifetime (in ledgers) a payment split entry is extended to, about 30 days.
+/// Expired entries are archived, not deleted, and can be restored.
+pub const PAYMENT_SPLIT_TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+/// Remaining lifetime (in ledgers) below which a payment split entry is extended
+pub const PAYMENT_SPLIT_TTL_THRESHOLD: u32 = PAYMENT_SPLIT_TTL_EXTEND_TO - DAY_IN_LEDGERS;
+
 /// Storage key variants for persistent storage.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
-    /// Store payment by ID
+    /// Legacy payment by global ID; moved to `PaymentRecord` by `migrate_payments`
     Payment(String),
-    /// Store payment record by agreement ID and payment number
+    /// Store payment record by agreement ID and payment number (1-based, in order)
     PaymentRecord(String, u32),
     /// Counter for total payments
     PaymentCount,
-    /// Platform fee collector address
-    PlatformFeeCollector,
+    /// Next legacy payment ID `migrate_payments` moves
+    MigrationCursor,
+    /// Set once no legacy payments are left to migrate
+    PaymentsMigrated,
+    /// Number of payment records kept for an agreement
+    AgreementPaymentCount(String),
+    /// Running total paid towards an agreement
+    AgreementTotalPaid(String),
+    /// Contract admin and configuration
+    State,
+    /// Payment split by agreement ID and month (0-based)
+    PaymentSplit(String, u32),
     /// Agreement storage (for reading agreement data)
     Agreement(String),
 }
@@ -1,9 +1,14 @@
 //! Tests for the Payment contract.
 
+use crate::errors::PaymentError;
+use crate::events::FeeCollectorUpdated;
 use crate::payment_impl::*;
+use crate::storage::DataKey;
 use crate::types::*;
-use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
-use soroban_sdk::{testutils::Address as _, Address, Env, Map, String};
+use crate::{PaymentContract, PaymentContractClient};
+use soroban_sdk::testutils::{Events, Ledger, MockAuth, MockAuthInvoke};
+use soroban_sdk::token::{StellarAssetClient as TokenAdminClient, TokenClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Event, IntoVal, String};
 
 // Helper function to create a test agreement
 fn create_test_agreement(
@@ -29,11 +34,11 @@ fn create_test_agreement(
         payment_count: 0,
         security_deposit: 0,
         start_date: 0,
-        end_date: 0,
+        end_date: 259_200_000,
         signed_at: None,
         payment_token,
         next_payment_due: 0,
-        payment_history: Map::new(env),
+        billing_cycle: BillingCycle::FixedPeriod(2_592_000),
     }
 }
 
@@ -80,12 +85,13 @@ fn test_create_payment_record() {
     let agreement_id = String::from_str(&env, "AGR_001");
 
     let record =
-        create_payment_record(&env, &agreement_id, 1000, 950, 50, &tenant, 1, 12345).unwrap();
+        create_payment_record(&env, &agreement_id, 1000, 850, 50, 100, &tenant, 1, 12345).unwrap();
 
     assert_eq!(record.agreement_id, agreement_id);
     assert_eq!(record.amount, 1000);
-    assert_eq!(record.landlord_amount, 950);
+    assert_eq!(record.landlord_amount, 850);
     assert_eq!(record.agent_amount, 50);
+    assert_eq!(record.platform_amount, 100);
     assert_eq!(record.payment_number, 1);
     assert_eq!(record.timestamp, 12345);
     assert_eq!(record.tenant, tenant);
@@ -146,3 +152,673 @@ fn test_agreement_with_agent() {
     assert_eq!(agreement.agent, Some(agent));
     assert_eq!(agreement.agent_commission_rate, 500);
 }
+
+fn create_contract(env: &Env) -> PaymentContractClient<'_> {
+    let contract_id = env.register(PaymentContract, ());
+    PaymentContractClient::new(env, &contract_id)
+}
+
+fn test_config(env: &Env, token: &Address) -> Config {
+    Config {
+        fee_bps: 1_000,
+        fee_collector: Address::generate(env),
+        allowed_tokens: vec![env, token.clone()],
+        paused: false,
+    }
+}
+
+fn store_agreement(env: &Env, client: &PaymentContractClient, agreement: &RentAgreement) {
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::Agreement(agreement.agreement_id.clone()),
+            agreement,
+        );
+    });
+}
+
+#[test]
+fn test_initialize_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    let config = test_config(&env, &Address::generate(&env));
+
+    client.initialize(&admin, &config);
+    let state = client.get_state().unwrap();
+    assert_eq!(state.admin, admin);
+    assert_eq!(state.config, config);
+    assert!(state.initialized);
+
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env), &config),
+        Err(Ok(PaymentError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_admin_changes_fee_collector() {
+    let env = Env::default();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    let config = test_config(&env, &Address::generate(&env));
+    env.mock_all_auths();
+    client.initialize(&admin, &config);
+
+    let collector = Address::generate(&env);
+    client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "set_platform_fee_collector",
+                args: (collector.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .set_platform_fee_collector(&collector);
+
+    let event = FeeCollectorUpdated {
+        old_collector: config.fee_collector,
+        new_collector: collector.clone(),
+        admin,
+    };
+    let published = (client.address.clone(), event.topics(&env), event.data(&env));
+    assert!(env.events().all().contains(published));
+    assert_eq!(client.get_state().unwrap().config.fee_collector, collector);
+}
+
+#[test]
+#[should_panic]
+fn test_collector_cannot_set_itself() {
+    let env = Env::default();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin, &test_config(&env, &Address::generate(&env)));
+
+    // Only the new collector authorizes, as the old entrypoint allowed
+    let attacker = Address::generate(&env);
+    client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "set_platform_fee_collector",
+                args: (attacker.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .set_platform_fee_collector(&attacker);
+}
+
+#[test]
+fn test_pay_rent_respects_pause_and_allowed_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    TokenAdminClient::new(&env, &token).mint(&tenant, &10_000);
+
+    let agreement_id = String::from_str(&env, "agreement_3");
+    assert_eq!(
+        client.try_pay_rent(&tenant, &agreement_id, &1000),
+        Err(Ok(PaymentError::InvalidState))
+    );
+
+    let mut config = test_config(&env, &token);
+    config.paused = true;
+    client.initialize(&admin, &config);
+    store_agreement(
+        &env,
+        &client,
+        &create_test_agreement(
+            &env,
+            "agreement_3",
+            &tenant,
+            &landlord,
+            None,
+            1000,
+            0,
+            AgreementStatus::Active,
+            token.clone(),
+        ),
+    );
+    assert_eq!(
+        client.try_pay_rent(&tenant, &agreement_id, &1000),
+        Err(Ok(PaymentError::ContractPaused))
+    );
+
+    config.paused = false;
+    config.allowed_tokens = vec![&env, Address::generate(&env)];
+    client.update_config(&config);
+    assert_eq!(
+        client.try_pay_rent(&tenant, &agreement_id, &1000),
+        Err(Ok(PaymentError::TokenNotAllowed))
+    );
+
+    config.allowed_tokens = vec![&env, token.clone()];
+    client.update_config(&config);
+    client.pay_rent(&tenant, &agreement_id, &1000);
+}
+
+#[test]
+fn test_pay_rent_splits_fee_commission_and_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    TokenAdminClient::new(&env, &token).mint(&tenant, &10_000);
+
+    let config = test_config(&env, &token);
+    client.initialize(&admin, &config);
+    store_agreement(
+        &env,
+        &client,
+        &create_test_agreement(
+            &env,
+            "agreement_4",
+            &tenant,
+            &landlord,
+            Some(agent.clone()),
+            1000,
+            500,
+            AgreementStatus::Active,
+            token.clone(),
+        ),
+    );
+
+    let agreement_id = String::from_str(&env, "agreement_4");
+    client.pay_rent(&tenant, &agreement_id, &1000);
+
+    let token_client = TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&config.fee_collector), 100);
+    assert_eq!(token_client.balance(&agent), 50);
+    assert_eq!(token_client.balance(&landlord), 850);
+    assert_eq!(token_client.balance(&tenant), 9_000);
+
+    let record = client.get_payment_record(&agreement_id, &1);
+    assert_eq!(record.amount, 1000);
+    assert_eq!(record.landlord_amount, 850);
+    assert_eq!(record.agent_amount, 50);
+    assert_eq!(record.platform_amount, 100);
+
+    let split = client.get_payment_split(&agreement_id, &0);
+    assert_eq!(split.landlord_amount, 850);
+    assert_eq!(split.agent_amount, 50);
+    assert_eq!(split.platform_amount, 100);
+}
+
+#[test]
+fn test_pay_rent_without_agent_pays_landlord_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    TokenAdminClient::new(&env, &token).mint(&tenant, &10_000);
+
+    let config = test_config(&env, &token);
+    client.initialize(&admin, &config);
+    // A commission rate without an agent is ignored
+    store_agreement(
+        &env,
+        &client,
+        &create_test_agreement(
+            &env,
+            "agreement_5",
+            &tenant,
+            &landlord,
+            None,
+            1000,
+            500,
+            AgreementStatus::Active,
+            token.clone(),
+        ),
+    );
+
+    let agreement_id = String::from_str(&env, "agreement_5");
+    client.pay_rent(&tenant, &agreement_id, &1000);
+
+    let token_client = TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&config.fee_collector), 100);
+    assert_eq!(token_client.balance(&landlord), 900);
+
+    let record = client.get_payment_record(&agreement_id, &1);
+    assert_eq!(record.agent_amount, 0);
+    assert_eq!(record.landlord_amount, 900);
+    assert_eq!(
+        client.try_get_payment_record(&agreement_id, &2),
+        Err(Ok(PaymentError::PaymentNotFound))
+    );
+}
+
+#[test]
+fn test_total_paid_and_history_past_ten_payments() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    TokenAdminClient::new(&env, &token).mint(&tenant, &100_000);
+
+    client.initialize(&Address::generate(&env), &test_config(&env, &token));
+    store_agreement(
+        &env,
+        &client,
+        &create_test_agreement(
+            &env,
+            "agreement_6",
+            &tenant,
+            &landlord,
+            None,
+            1000,
+            0,
+            AgreementStatus::Active,
+            token.clone(),
+        ),
+    );
+
+    let agreement_id = String::from_str(&env, "agreement_6");
+    for _ in 0..12 {
+        client.pay_rent(&tenant, &agreement_id, &1000);
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + 2_592_000);
+    }
+
+    assert_eq!(client.get_total_paid(&agreement_id), 12_000);
+    assert_eq!(client.get_payment_count(), 12);
+    assert_eq!(
+        client.get_total_paid(&String::from_str(&env, "agreement_7")),
+        0
+    );
+
+    let first = client.get_payment_history(&agreement_id, &0, &5);
+    assert_eq!(first.records.len(), 5);
+    assert_eq!(first.records.get_unchecked(0).payment_number, 1);
+    assert_eq!(first.next_cursor, Some(5));
+
+    let last = client.get_payment_history(&agreement_id, &10, &5);
+    assert_eq!(last.records.len(), 2);
+    assert_eq!(last.records.get_unchecked(1).payment_number, 12);
+    assert_eq!(last.next_cursor, None);
+}
+
+#[test]
+fn test_migrate_legacy_payments() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    client.initialize(&Address::generate(&env), &test_config(&env, &token));
+
+    // Legacy payments 0..=12 alternate between two agreements; 11 and 12 share the
+    // "unknown" key, so only 12 is left
+    let agreement_a = String::from_str(&env, "legacy_a");
+    let agreement_b = String::from_str(&env, "legacy_b");
+    env.as_contract(&client.address, || {
+        for i in 0..13u32 {
+            let agreement_id = if i % 2 == 0 {
+                &agreement_a
+            } else {
+                &agreement_b
+            };
+            let record = create_payment_record(
+                &env,
+                agreement_id,
+                100 + i as i128,
+                100 + i as i128,
+                0,
+                0,
+                &tenant,
+                i,
+                i as u64,
+            )
+            .unwrap();
+            env.storage()
+                .persistent()
+                .set(&DataKey::Payment(payment_id(&env, i)), &record);
+        }
+        env.storage().instance().set(&DataKey::PaymentCount, &13u32);
+    });
+
+    // New payments wait until the legacy ones are in place
+    let landlord = Address::generate(&env);
+    TokenAdminClient::new(&env, &token).mint(&tenant, &1_000);
+    store_agreement(
+        &env,
+        &client,
+        &create_test_agreement(
+            &env,
+            "legacy_a",
+            &tenant,
+            &landlord,
+            None,
+            1_000,
+            0,
+            AgreementStatus::Active,
+            token.clone(),
+        ),
+    );
+    assert_eq!(
+        client.try_pay_rent(&tenant, &agreement_a, &1_000),
+        Err(Ok(PaymentError::MigrationPending))
+    );
+
+    assert_eq!(client.migrate_payments(&8), 8);
+    assert_eq!(
+        client.try_pay_rent(&tenant, &agreement_a, &1_000),
+        Err(Ok(PaymentError::MigrationPending))
+    );
+    assert_eq!(client.migrate_payments(&8), 13);
+    // Progress is kept, so a rerun changes nothing
+    assert_eq!(client.migrate_payments(&20), 13);
+
+    // 100 + 102 + ... + 112 and 101 + 103 + ... + 109
+    assert_eq!(client.get_total_paid(&agreement_a), 742);
+    assert_eq!(client.get_total_paid(&agreement_b), 525);
+
+    let history = client.get_payment_history(&agreement_a, &0, &50);
+    assert_eq!(history.records.len(), 7);
+    let last = history.records.get_unchecked(6);
+    assert_eq!(last.amount, 112);
+    assert_eq!(last.payment_number, 7);
+    assert_eq!(client.get_payment_record(&agreement_a, &7), last);
+    assert_eq!(client.get_payment_record(&agreement_b, &5).amount, 109);
+    assert_eq!(
+        client.try_get_payment_record(&agreement_b, &6),
+        Err(Ok(PaymentError::PaymentNotFound))
+    );
+
+    // The first new payment lands after the migrated history and pays period 7
+    env.ledger().set_timestamp(7 * 2_592_000 - 1);
+    assert_eq!(
+        client.try_pay_rent(&tenant, &agreement_a, &1_000),
+        Err(Ok(PaymentError::PaymentNotDue))
+    );
+    env.ledger().set_timestamp(7 * 2_592_000);
+    client.pay_rent(&tenant, &agreement_a, &1_000);
+    let newest = client.get_payment_record(&agreement_a, &8);
+    assert_eq!(newest.amount, 1_000);
+    assert_eq!(client.get_payment_count(), 14);
+}
+
+#[test]
+fn test_migrate_agreements_moves_legacy_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    client.initialize(&Address::generate(&env), &test_config(&env, &token));
+
+    let agreement_id = String::from_str(&env, "legacy_agreement");
+    let mut history = soroban_sdk::Map::new(&env);
+    for month in 0..2u32 {
+        history.set(
+            month,
+            LegacyPaymentSplit {
+                landlord_amount: 900,
+                platform_amount: 100,
+                token: token.clone(),
+                payment_date: 1_000 + month as u64,
+            },
+        );
+    }
+    let legacy = LegacyRentAgreement {
+        agreement_id: agreement_id.clone(),
+        landlord: Address::generate(&env),
+        tenant: tenant.clone(),
+        agent: None,
+        monthly_rent: 1_000,
+        security_deposit: 0,
+        start_date: 0,
+        end_date: 31_536_000,
+        agent_commission_rate: 0,
+        status: AgreementStatus::Active,
+        total_rent_paid: 0,
+        payment_count: 0,
+        signed_at: None,
+        payment_token: token.clone(),
+        next_payment_due: 2_592_000,
+        payment_history: history,
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Agreement(agreement_id.clone()), &legacy);
+    });
+
+    let ids = vec![&env, agreement_id.clone()];
+    assert_eq!(client.migrate_agreements(&ids), 1);
+    // Already migrated agreements are skipped
+    assert_eq!(client.migrate_agreements(&ids), 0);
+
+    let split = client.get_payment_split(&agreement_id, &1);
+    assert_eq!(split.landlord_amount, 900);
+    assert_eq!(split.agent_amount, 0);
+    assert_eq!(split.payment_date, 1_001);
+
+    assert_eq!(client.get_total_paid(&agreement_id), 2_000);
+    let record = client.get_payment_record(&agreement_id, &2);
+    assert_eq!(record.amount, 1_000);
+    assert_eq!(record.platform_amount, 100);
+    assert_eq!(record.timestamp, 1_001);
+    assert_eq!(record.tenant, tenant);
+
+    let agreement: RentAgreement = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Agreement(agreement_id.clone()))
+            .unwrap()
+    });
+    assert_eq!(agreement.billing_cycle, BillingCycle::CalendarMonth);
+    // 1970-03-01, two calendar months after the start date
+    assert_eq!(agreement.next_payment_due, 5_097_600);
+}
+
+#[test]
+fn test_payment_id_matches_legacy_keys() {
+    let env = Env::default();
+    assert_eq!(payment_id(&env, 0), String::from_str(&env, "0"));
+    assert_eq!(payment_id(&env, 10), String::from_str(&env, "10"));
+    assert_eq!(payment_id(&env, 11), String::from_str(&env, "unknown"));
+    assert_eq!(
+        payment_id(&env, u32::MAX),
+        String::from_str(&env, "unknown")
+    );
+}
+
+#[test]
+fn test_pay_rent_writes_do_not_grow_with_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    TokenAdminClient::new(&env, &token).mint(&tenant, &100_000);
+
+    client.initialize(&Address::generate(&env), &test_config(&env, &token));
+    store_agreement(
+        &env,
+        &client,
+        &create_test_agreement(
+            &env,
+            "agreement_8",
+            &tenant,
+            &landlord,
+            None,
+            1000,
+            0,
+            AgreementStatus::Active,
+            token.clone(),
+        ),
+    );
+
+    let agreement_id = String::from_str(&env, "agreement_8");
+    let mut write_bytes = [0u32; 24];
+    for bytes in write_bytes.iter_mut() {
+        client.pay_rent(&tenant, &agreement_id, &1000);
+        *bytes = env.cost_estimate().resources().write_bytes;
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + 2_592_000);
+    }
+    assert_eq!(write_bytes[1], write_bytes[23]);
+
+    let split = client.get_payment_split(&agreement_id, &23);
+    assert_eq!(split.landlord_amount, 900);
+    assert_eq!(
+        client.try_get_payment_split(&agreement_id, &24),
+        Err(Ok(PaymentError::PaymentNotFound))
+    );
+}
+
+#[test]
+fn test_calendar_month_schedule_clamps_short_months() {
+    let env = Env::default();
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+
+    // 2024-01-31 until 2024-05-01 (plus one hour into the day)
+    let mut agreement = create_test_agreement(
+        &env,
+        "agreement_9",
+        &tenant,
+        &landlord,
+        None,
+        1000,
+        0,
+        AgreementStatus::Active,
+        token,
+    );
+    agreement.start_date = 1_706_659_200 + 3_600;
+    agreement.end_date = 1_714_521_600;
+    agreement.billing_cycle = BillingCycle::CalendarMonth;
+    store_agreement(&env, &client, &agreement);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::AgreementPaymentCount(agreement.agreement_id.clone()),
+            &1u32,
+        );
+    });
+
+    let schedule = client.get_payment_schedule(&String::from_str(&env, "agreement_9"));
+    let expected = [1_706_659_200, 1_709_164_800, 1_711_843_200, 1_714_435_200];
+    assert_eq!(schedule.len(), 4);
+    for (period, due_date) in expected.iter().enumerate() {
+        let payment = schedule.get_unchecked(period as u32);
+        assert_eq!(payment.period, period as u32);
+        assert_eq!(payment.due_date, due_date + 3_600);
+        assert_eq!(payment.amount, 1000);
+        assert_eq!(payment.paid, period == 0);
+    }
+}
+
+#[test]
+fn test_late_payment_does_not_shift_due_dates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    TokenAdminClient::new(&env, &token).mint(&tenant, &10_000);
+
+    client.initialize(&Address::generate(&env), &test_config(&env, &token));
+    let mut agreement = create_test_agreement(
+        &env,
+        "agreement_10",
+        &tenant,
+        &landlord,
+        None,
+        1000,
+        0,
+        AgreementStatus::Active,
+        token,
+    );
+    agreement.start_date = 1_000_000;
+    agreement.end_date = 1_000_000 + 12 * 2_592_000;
+    store_agreement(&env, &client, &agreement);
+
+    // First payment ten days late
+    let agreement_id = String::from_str(&env, "agreement_10");
+    env.ledger().set_timestamp(1_000_000 + 10 * 86_400);
+    client.pay_rent(&tenant, &agreement_id, &1000);
+
+    env.ledger().set_timestamp(1_000_000 + 2_592_000 - 1);
+    assert_eq!(
+        client.try_pay_rent(&tenant, &agreement_id, &1000),
+        Err(Ok(PaymentError::PaymentNotDue))
+    );
+    env.ledger().set_timestamp(1_000_000 + 2_592_000);
+    client.pay_rent(&tenant, &agreement_id, &1000);
+
+    let schedule = client.get_payment_schedule(&agreement_id);
+    assert_eq!(schedule.len(), 12);
+    assert!(schedule.get_unchecked(1).paid);
+    assert!(!schedule.get_unchecked(2).paid);
+    assert_eq!(
+        schedule.get_unchecked(2).due_date,
+        1_000_000 + 2 * 2_592_000
+    );
+
+    agreement.billing_cycle = BillingCycle::FixedPeriod(0);
+    store_agreement(&env, &client, &agreement);
+    assert_eq!(
+        client.try_get_payment_schedule(&agreement_id),
+        Err(Ok(PaymentError::InvalidBillingCycle))
+    );
+}
+
+#[test]
+fn test_pay_rent_stops_at_end_date() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    TokenAdminClient::new(&env, &token).mint(&tenant, &10_000);
+
+    client.initialize(&Address::generate(&env), &test_config(&env, &token));
+    let mut agreement = create_test_agreement(
+        &env,
+        "agreement_11",
+        &tenant,
+        &landlord,
+        None,
+        1000,
+        0,
+        AgreementStatus::Active,
+        token,
+    );
+    agreement.end_date = 2 * 2_592_000;
+    store_agreement(&env, &client, &agreement);
+
+    // Two periods fit before the end date
+    let agreement_id = String::from_str(&env, "agreement_11");
+    client.pay_rent(&tenant, &agreement_id, &1000);
+    env.ledger().set_timestamp(2_592_000);
+    client.pay_rent(&tenant, &agreement_id, &1000);
+
+    env.ledger().set_timestamp(3 * 2_592_000);
+    assert_eq!(
+        client.try_pay_rent(&tenant, &agreement_id, &1000),
+        Err(Ok(PaymentError::AgreementEnded))
+    );
+    assert_eq!(client.get_total_paid(&agreement_id), 2000);
+}
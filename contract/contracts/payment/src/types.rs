@@ -1,5 +1,5 @@
 //! Data structures for the Payment contract.
-use soroban_sdk::{contracttype, Address, Map, String};
+use soroban_sdk::{contracttype, Address, Map, String, Vec};
 
 /// Payment record for tracking individual payments
 #[contracttype]
@@ -10,6 +10,7 @@ pub struct PaymentRecord {
     pub amount: i128,
     pub landlord_amount: i128,
     pub agent_amount: i128,
+    pub platform_amount: i128,
     pub timestamp: u64,
     pub tenant: Address,
 }
@@ -19,11 +20,22 @@ pub struct PaymentRecord {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PaymentSplit {
     pub landlord_amount: i128,
+    pub agent_amount: i128,
     pub platform_amount: i128,
     pub token: Address,
     pub payment_date: u64,
 }
 
+/// One page of an agreement's payment history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentPage {
+    /// Payments on this page, oldest first
+    pub records: Vec<PaymentRecord>,
+    /// Cursor to pass for the next page; None when this is the last page
+    pub next_cursor: Option<u32>,
+}
+
 /// Agreement status enum (needed for payment validation)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -37,10 +49,56 @@ pub enum AgreementStatus {
     Disputed,
 }
 
+/// How an agreement's due dates are spaced from its start date
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BillingCycle {
+    /// Same day of month as the start date, clamped to the last day of short months
+    CalendarMonth,
+    /// Fixed-length periods of the given number of seconds
+    FixedPeriod(u64),
+}
+
+/// One due payment in an agreement's schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledPayment {
+    /// Period index, 0 for the first payment
+    pub period: u32,
+    pub due_date: u64,
+    pub amount: i128,
+    pub paid: bool,
+}
+
 /// Rent agreement structure (needed for payment processing)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RentAgreement {
+    pub agreement_id: String,
+    pub landlord: Address,
+    pub tenant: Address,
+    pub agent: Option<Address>,
+    pub monthly_rent: i128,
+    pub security_deposit: i128,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub agent_commission_rate: u32,
+    pub status: AgreementStatus,
+    /// Not kept up to date here; `get_total_paid` has the running total
+    pub total_rent_paid: i128,
+    /// Not kept up to date here; payments are counted by the agreement's payment list
+    pub payment_count: u32,
+    pub signed_at: Option<u64>,
+    pub payment_token: Address,
+    pub next_payment_due: u64,
+    pub billing_cycle: BillingCycle,
+}
+
+/// Agreement layout that embedded every payment split, kept only so
+/// `migrate_agreements` can read agreements stored before the split entries moved out.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRentAgreement {
     pub agreement_id: String,
     pub landlord: Address,
     pub tenant: Address,
@@ -56,5 +114,38 @@ pub struct RentAgreement {
     pub signed_at: Option<u64>,
     pub payment_token: Address,
     pub next_payment_due: u64,
-    pub payment_history: Map<u32, PaymentSplit>,
+    pub payment_history: Map<u32, LegacyPaymentSplit>,
+}
+
+/// Payment split as embedded in `LegacyRentAgreement`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyPaymentSplit {
+    pub landlord_amount: i128,
+    pub platform_amount: i128,
+    pub token: Address,
+    pub payment_date: u64,
+}
+
+/// Payment contract configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Platform fee in basis points of each rent payment
+    pub fee_bps: u32,
+    /// Address receiving the platform fee
+    pub fee_collector: Address,
+    /// Tokens rent may be paid in
+    pub allowed_tokens: Vec<Address>,
+    /// Whether payments are paused
+    pub paused: bool,
+}
+
+/// Contract admin and configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractState {
+    pub admin: Address,
+    pub config: Config,
+    pub initialized: bool,
 }